@@ -0,0 +1,210 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Field, Fields, Lit, LitStr, Meta, NestedMeta, Type};
+
+/// Methods that can be derived, because they are sent without a body.
+const METHODS: &[(&str, &str)] = &[
+    ("GET", "Get"),
+    ("HEAD", "Head"),
+    ("DELETE", "Delete"),
+    ("OPTIONS", "Options"),
+    ("TRACE", "Trace"),
+];
+
+pub fn fetch_request_derive_handler(input: DeriveInput) -> TokenStream {
+    match generate(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// The options given by the struct's `#[fetch(...)]` attributes.
+struct RequestOptions {
+    url: LitStr,
+    method: Ident,
+    response: Type,
+    format: Type,
+}
+
+fn generate(input: DeriveInput) -> Result<TokenStream, Error> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    let RequestOptions {
+        url,
+        method,
+        response,
+        format,
+    } = request_options(&ident, &attrs)?;
+    let query_params = match data {
+        Data::Struct(data) => query_params(&data.fields)?,
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "FetchRequest can only be derived for structs",
+            ))
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::yewtil::fetch::FetchRequest for #ident #ty_generics #where_clause {
+            type RequestBody = ();
+            type ResponseBody = #response;
+            type Format = #format;
+
+            fn url(&self) -> ::std::string::String {
+                ::std::string::String::from(#url)
+            }
+
+            fn method(&self) -> ::yewtil::fetch::MethodBody<Self::RequestBody> {
+                ::yewtil::fetch::MethodBody::#method
+            }
+
+            fn headers(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                ::std::vec::Vec::new()
+            }
+
+            fn query(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                let mut query = ::std::vec::Vec::new();
+                #(#query_params)*
+                query
+            }
+        }
+    })
+}
+
+/// Collects the options from the struct's `#[fetch(...)]` attributes.
+fn request_options(ident: &Ident, attrs: &[Attribute]) -> Result<RequestOptions, Error> {
+    let mut url = None;
+    let mut method = None;
+    let mut response = None;
+    let mut format = None;
+
+    for nested in fetch_attributes(attrs)? {
+        let (name, value) = match &nested {
+            NestedMeta::Meta(Meta::NameValue(pair)) => match (pair.path.get_ident(), &pair.lit) {
+                (Some(name), Lit::Str(value)) => (name.to_string(), value.clone()),
+                _ => return Err(Error::new_spanned(nested, "expected a string, eg: url = \"/apples\"")),
+            },
+            _ => return Err(Error::new_spanned(nested, "expected an option, eg: url = \"/apples\"")),
+        };
+        match name.as_str() {
+            "url" => url = Some(value),
+            "method" => method = Some(method_variant(&value)?),
+            "response" => response = Some(value.parse()?),
+            "format" => format = Some(value.parse()?),
+            _ => {
+                return Err(Error::new_spanned(
+                    nested,
+                    "unknown option, expected one of: url, method, response, format",
+                ))
+            }
+        }
+    }
+
+    Ok(RequestOptions {
+        url: url.ok_or_else(|| {
+            Error::new_spanned(ident, "the URL must be given using #[fetch(url = \"...\")]")
+        })?,
+        method: method.unwrap_or_else(|| Ident::new("Get", Span::call_site())),
+        response: response.ok_or_else(|| {
+            Error::new_spanned(
+                ident,
+                "the response body's type must be given using #[fetch(response = \"...\")]",
+            )
+        })?,
+        format: format.unwrap_or_else(|| syn::parse_quote!(::yewtil::fetch::Json)),
+    })
+}
+
+/// Gets the `MethodBody` variant for the name of the method.
+fn method_variant(method: &LitStr) -> Result<Ident, Error> {
+    let name = method.value().to_uppercase();
+    METHODS
+        .iter()
+        .find(|(method, _)| *method == name)
+        .map(|(_, variant)| Ident::new(variant, method.span()))
+        .ok_or_else(|| {
+            Error::new_spanned(
+                method,
+                "only methods without a body (GET, HEAD, DELETE, OPTIONS, TRACE) can be derived, \
+                 implement FetchRequest manually for other methods",
+            )
+        })
+}
+
+/// Creates the statements that add each `#[fetch(query)]` field to the query.
+///
+/// `Option` fields are only added when they are `Some`.
+fn query_params(fields: &Fields) -> Result<Vec<TokenStream>, Error> {
+    let mut params = Vec::new();
+    for field in fields {
+        if !is_query_field(field)? {
+            continue;
+        }
+        let name = match &field.ident {
+            Some(name) => name,
+            None => {
+                return Err(Error::new_spanned(
+                    field,
+                    "query parameters must be named fields",
+                ))
+            }
+        };
+        let key = name.to_string();
+        params.push(if is_option(&field.ty) {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#name {
+                    query.push((::std::string::String::from(#key), value.to_string()));
+                }
+            }
+        } else {
+            quote! {
+                query.push((::std::string::String::from(#key), self.#name.to_string()));
+            }
+        });
+    }
+    Ok(params)
+}
+
+/// Determines if the field is marked with `#[fetch(query)]`.
+fn is_query_field(field: &Field) -> Result<bool, Error> {
+    let mut is_query = false;
+    for nested in fetch_attributes(&field.attrs)? {
+        match &nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("query") => is_query = true,
+            _ => return Err(Error::new_spanned(nested, "expected #[fetch(query)]")),
+        }
+    }
+    Ok(is_query)
+}
+
+/// Gets the options listed within any `#[fetch(...)]` attributes.
+fn fetch_attributes(attrs: &[Attribute]) -> Result<Vec<NestedMeta>, Error> {
+    let mut nested = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("fetch")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => nested.extend(list.nested),
+            _ => return Err(Error::new_spanned(attr, "expected options, eg: #[fetch(url = \"...\")]")),
+        }
+    }
+    Ok(nested)
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
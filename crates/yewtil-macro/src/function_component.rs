@@ -1,6 +1,6 @@
 use proc_macro2::{TokenStream, Ident, Span};
 use proc_macro::TokenStream as TokenStream1;
-use syn::{Visibility, Error, Field, Stmt, Block, VisPublic, Type};
+use syn::{Visibility, Error, Field, Stmt, Block, VisPublic, Type, Generics, Attribute};
 use syn::Token;
 use syn::token;
 use syn::punctuated::Punctuated;
@@ -11,9 +11,15 @@ use syn::export::ToTokens;
 use quote::quote;
 
 pub fn function_component_handler(attr: TokenStream, item: TokenStream1) -> TokenStream1 {
-    let component_name = attr.to_string();
-    assert!(!component_name.is_empty(), "you must provide a component name. eg: function_component(MyComponent)");
-    let component_name = Ident::new(&component_name, Span::call_site());
+    assert!(!attr.is_empty(), "you must provide a component name. eg: function_component(MyComponent)");
+    let attr = match syn::parse2::<FunctionComponentAttr>(attr) {
+        Ok(attr) => attr,
+        Err(err) => return TokenStream1::from(err.to_compile_error())
+    };
+    let component_name = attr.component_name;
+    let impl_name = attr.impl_name.unwrap_or_else(|| {
+        Ident::new(&format!("FuncComp{}", component_name), Span::call_site())
+    });
 
     let item_copy = item.clone();
 
@@ -21,22 +27,49 @@ pub fn function_component_handler(attr: TokenStream, item: TokenStream1) -> Toke
 
     TokenStream1::from(FunctionComponentInfo {
         component_name,
+        impl_name,
         function
     }.to_token_stream())
 }
 
+/// The arguments to the attribute: the name of the component,
+/// and optionally the name of the props struct that implements `PureComponent`.
+///
+/// eg. `function_component(Button)` or `function_component(Button, impl = ButtonInner)`.
+pub struct FunctionComponentAttr {
+    component_name: Ident,
+    impl_name: Option<Ident>
+}
+
+impl Parse for FunctionComponentAttr {
+    fn parse(input: &ParseBuffer) -> Result<Self, Error> {
+        let component_name = input.parse()?;
+        let impl_name = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            input.parse::<Token![impl]>()?;
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(FunctionComponentAttr {
+            component_name,
+            impl_name
+        })
+    }
+}
+
 pub struct FunctionComponentInfo {
     component_name: Ident,
+    impl_name: Ident,
     function: Function
 }
 
 
-// TODO, support type parameters
-
 pub struct Function {
     pub vis: Visibility,
     pub fn_token: Token![fn],
     pub name: Ident,
+    pub generics: Generics,
     pub paren_token: token::Paren,
     pub fields: Punctuated<Field, Token![,]>,
     pub returns_token: Token![->],
@@ -49,14 +82,24 @@ impl Parse for Function {
     fn parse(input: &ParseBuffer) -> Result<Self, Error> {
         let content;
         let content2;
+        let vis = input.parse()?;
+        let fn_token = input.parse()?;
+        let name = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let paren_token = parenthesized!(content in input);
+        let fields = content.parse_terminated(Field::parse_named)?;
+        let returns_token = input.parse()?;
+        let return_ty = input.parse()?;
+        generics.where_clause = input.parse()?;
         Ok(Function {
-            vis: input.parse()?,
-            fn_token: input.parse()?,
-            name: input.parse()?,
-            paren_token: parenthesized!(content in input),
-            fields: content.parse_terminated(Field::parse_named)?,
-            returns_token: input.parse()?,
-            return_ty: input.parse()?,
+            vis,
+            fn_token,
+            name,
+            generics,
+            paren_token,
+            fields,
+            returns_token,
+            return_ty,
             brace_token: braced!(content2 in input),
             body: content2.call(Block::parse_within)?
         })
@@ -66,18 +109,25 @@ impl Parse for Function {
 impl ToTokens for Function {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Function {
-            vis, fn_token, name, fields, returns_token, return_ty,  body, ..
+            vis, fn_token, name, generics, fields, returns_token, return_ty,  body, ..
         } = self;
+        let where_clause = &generics.where_clause;
+        // Props are lent to the function, so owned types are taken by reference to avoid cloning them.
         let fields = fields.iter()
             .map(|field: &Field| {
                 let mut new_field: Field = field.clone();
-                new_field.attrs = vec![];
+                // Attributes for the props struct aren't allowed on function arguments.
+                new_field.attrs.retain(|attr| !is_props_attribute(attr));
+                if !is_reference(&field.ty) && !is_copy_primitive(&field.ty) {
+                    let ty = &field.ty;
+                    new_field.ty = syn::parse_quote!(&#ty);
+                }
                 new_field
             })
             .collect::<Punctuated<_, Token![,]>>();
 
         tokens.extend(quote! {
-            #vis #fn_token #name(#fields) #returns_token #return_ty {
+            #vis #fn_token #name #generics (#fields) #returns_token #return_ty #where_clause {
                 #(#body)*
             }
         })
@@ -87,26 +137,25 @@ impl ToTokens for Function {
 impl ToTokens for FunctionComponentInfo {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let FunctionComponentInfo {
-            component_name, function
+            component_name, impl_name, function
         } = self;
         // The function tokens must be re-generated in order to strip the attributes that are not allowed.
         let function_token_stream = function.to_token_stream();
         let Function {
-            vis, name, fields, ..
+            vis, name, generics, fields, ..
         } = function;
-
-        let impl_name = format!("FuncComp{}", component_name.to_string());
-        let impl_name = Ident::new(&impl_name, Span::call_site());
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         let alias = quote! {
-            #vis type #component_name = ::yewtil::Pure<#impl_name>;
+            #vis type #component_name #ty_generics = ::yewtil::Pure<#impl_name #ty_generics>;
         };
 
         // Set the fields to be public and strips references as necessary.
-        // This will preserve attributes like #[props(required)], which will appear in the generated struct below.
+        // This will preserve attributes like #[props(required)] and doc comments, which will appear in the generated struct below.
         let new_fields = fields.iter()
             .map(|field: &Field| {
                 let mut new_field: Field = field.clone();
+                new_field.attrs.retain(is_props_attribute);
                 let visibility = Visibility::Public(VisPublic{ pub_token: syn::token::Pub {span: Span::call_site()} });
                 // Strip references so the component can have a static lifetime.
                 // TODO Handle 'static lifetimes gracefully here - allowing &'static strings instead of erroneously converting them to plain strs.
@@ -128,33 +177,31 @@ impl ToTokens for FunctionComponentInfo {
 
         let component_struct = quote!{
             #[derive(::std::clone::Clone, ::std::cmp::PartialEq, ::yew::Properties)]
-            #vis struct #impl_name {
+            #vis struct #impl_name #impl_generics #where_clause {
                 #new_fields
             }
         };
 
-        let arguments = fields.iter().zip(new_fields.iter())
-            .map(|(field, new_field): (&Field, &Field)| {
+        let arguments = fields.iter()
+            .map(|field: &Field| {
                 let field_name = field.ident.as_ref().expect("Field must have name");
 
-                // If the fields differ, then a reference was removed from the function's field's type
-                // to make it static.
-                // Otherwise it is assumed that the type is not a reference on the function and it
-                // implements clone, and that when calling the function, the type should be cloned again.
-                if field.ty != new_field.ty {
+                // Primitives are cheap to copy, so they are passed by value.
+                // Everything else is passed by reference, matching the function generated above.
+                if is_copy_primitive(&field.ty) {
                     quote! {
-                        &self.#field_name
+                        self.#field_name
                     }
                 } else {
                     quote! {
-                        self.#field_name.clone()
+                        &self.#field_name
                     }
                 }
             })
             .collect::<Punctuated<_, Token![,]>>();
 
         let pure_component_impl = quote! {
-            impl ::yewtil::PureComponent for #impl_name {
+            impl #impl_generics ::yewtil::PureComponent for #impl_name #ty_generics #where_clause {
                 fn render(&self) -> ::yew::Html {
                      #name(#arguments)
                 }
@@ -169,4 +216,36 @@ impl ToTokens for FunctionComponentInfo {
             #pure_component_impl
         })
     }
-}
\ No newline at end of file
+}
+
+/// Determines if the attribute belongs on the field of the props struct, rather than the function argument.
+///
+/// These are `#[props(...)]` attributes and doc comments.
+/// Any other attributes (eg. `#[allow(unused_variables)]`) are left on the function argument.
+fn is_props_attribute(attr: &Attribute) -> bool {
+    attr.path.is_ident("props") || attr.path.is_ident("doc")
+}
+
+fn is_reference(ty: &Type) -> bool {
+    matches!(ty, Type::Reference(_))
+}
+
+/// Determines if the type is a primitive that implements `Copy`.
+///
+/// Other types may also implement `Copy`, but that can't be known from within the macro.
+fn is_copy_primitive(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char",
+        "i8", "i16", "i32", "i64", "i128", "isize",
+        "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.get_ident()
+                .map(|ident| PRIMITIVES.iter().any(|primitive| ident == primitive))
+                .unwrap_or(false)
+        }
+        _ => false
+    }
+}
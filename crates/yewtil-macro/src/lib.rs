@@ -2,10 +2,54 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 
 
+use crate::fetch_request_derive::fetch_request_derive_handler;
 use crate::function_component::function_component_handler;
+use crate::pure_derive::pure_derive_handler;
+use syn::parse_macro_input;
 
+mod fetch_request_derive;
 mod function_component;
+mod pure_derive;
+/// Creates a pure component out of a function, whose arguments become the component's props.
+///
+/// Props are passed to the function by reference, so an argument declared as an owned type
+/// (eg. `text: String`) will be received as a reference (`&String`) to avoid cloning it on every render.
+/// Primitives like `usize` and `bool` are passed by value.
+///
+/// `#[props(...)]` attributes and doc comments on arguments are moved to the fields of the generated props struct,
+/// so `#[props(required)]` can be used to make a prop required.
+/// Other attributes are kept on the arguments.
+///
+/// The props struct is named `FuncComp` followed by the component's name (eg. `FuncCompButton`),
+/// which can be changed to avoid collisions by naming it explicitly: `#[function_component(Button, impl = ButtonInner)]`.
 #[proc_macro_attribute]
 pub fn function_component(attr: TokenStream, item: TokenStream) -> TokenStream {
     function_component_handler(attr.into(), item.into()).into()
 }
+
+/// Implements `PureComponent` for a props struct that has a `view` method of its own,
+/// and creates a type alias for the resulting component.
+///
+/// The component is named after the struct without its `Pure` prefix (`PureButton` becomes `Button`),
+/// or can be named explicitly using `#[pure(Button)]`.
+/// A method other than `view` can render the component by naming it using `#[pure(render = "draw")]`.
+/// The struct must still derive `Properties` and `PartialEq`.
+#[proc_macro_derive(Pure, attributes(pure))]
+pub fn pure(input: TokenStream) -> TokenStream {
+    pure_derive_handler(parse_macro_input!(input)).into()
+}
+
+/// Implements `FetchRequest` for simple requests that are sent without a body.
+///
+/// The request is described by `#[fetch(...)]` attributes on the struct:
+/// * `url` - The URL of the resource.
+/// * `response` - The type of the response's body.
+/// * `method` - The HTTP method, which must not have a body. Defaults to `GET`.
+/// * `format` - The `Format` the response is deserialized with. Defaults to `Json`.
+///
+/// Fields marked with `#[fetch(query)]` are added to the query string, using their `Display` implementation.
+/// `Option` fields are left out when they are `None`.
+#[proc_macro_derive(FetchRequest, attributes(fetch))]
+pub fn fetch_request(input: TokenStream) -> TokenStream {
+    fetch_request_derive_handler(parse_macro_input!(input)).into()
+}
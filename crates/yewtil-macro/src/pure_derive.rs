@@ -0,0 +1,112 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, DeriveInput, Error, Lit, Meta, NestedMeta};
+
+const PREFIX: &str = "Pure";
+/// The inherent method that renders the component, unless another is named using `#[pure(render = "...")]`.
+const DEFAULT_RENDER: &str = "view";
+
+/// The options given by the struct's `#[pure(...)]` attributes.
+struct PureOptions {
+    component_name: Option<Ident>,
+    render: Option<Ident>,
+}
+
+pub fn pure_derive_handler(input: DeriveInput) -> TokenStream {
+    match generate(input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn generate(input: DeriveInput) -> Result<TokenStream, Error> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        ..
+    } = input;
+
+    let options = pure_options(&attrs)?;
+    let component_name = match options.component_name {
+        Some(name) => name,
+        None => component_name_from_prefix(&ident)?,
+    };
+    let render = options
+        .render
+        .unwrap_or_else(|| Ident::new(DEFAULT_RENDER, Span::call_site()));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #vis type #component_name #ty_generics = ::yewtil::Pure<#ident #ty_generics>;
+
+        impl #impl_generics ::yewtil::PureComponent for #ident #ty_generics #where_clause {
+            fn render(&self) -> ::yew::Html {
+                // This is named differently to `PureComponent::render`,
+                // so a missing method is a compile error, instead of a recursive call.
+                #ident::#render(self)
+            }
+        }
+    })
+}
+
+/// Collects the options from the struct's `#[pure(...)]` attributes.
+fn pure_options(attrs: &[Attribute]) -> Result<PureOptions, Error> {
+    let mut options = PureOptions {
+        component_name: None,
+        render: None,
+    };
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("pure")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            _ => return Err(Error::new_spanned(attr, EXPECTED_OPTIONS)),
+        };
+        for nested in nested {
+            match &nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                    options.component_name = path.get_ident().cloned();
+                }
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("render") => {
+                    options.render = Some(render_method(&pair.lit)?);
+                }
+                _ => return Err(Error::new_spanned(nested, EXPECTED_OPTIONS)),
+            }
+        }
+    }
+    Ok(options)
+}
+
+const EXPECTED_OPTIONS: &str =
+    "expected the name of the component and/or its render method, eg: #[pure(Button, render = \"draw\")]";
+
+/// Gets the name of the inherent method that renders the component.
+fn render_method(lit: &Lit) -> Result<Ident, Error> {
+    let name = match lit {
+        Lit::Str(name) => name,
+        _ => return Err(Error::new_spanned(lit, "expected a string, eg: render = \"draw\"")),
+    };
+    let method: Ident = name.parse()?;
+    if method == "render" {
+        // `#ident::render` would call `PureComponent::render` itself if the inherent method were missing.
+        return Err(Error::new_spanned(
+            name,
+            "the render method can't be named `render`, as it would be confused with `PureComponent::render`",
+        ));
+    }
+    Ok(method)
+}
+
+/// Names the component after the struct, without its `Pure` prefix.
+fn component_name_from_prefix(ident: &Ident) -> Result<Ident, Error> {
+    let name = ident.to_string();
+    if name.starts_with(PREFIX) && name.len() > PREFIX.len() {
+        Ok(Ident::new(&name[PREFIX.len()..], Span::call_site()))
+    } else {
+        Err(Error::new_spanned(
+            ident,
+            "the struct's name must start with `Pure` (eg: `PureButton` for a `Button` component), \
+             or the component's name must be given using #[pure(Button)]",
+        ))
+    }
+}
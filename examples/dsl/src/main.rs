@@ -1,8 +1,10 @@
-use yew::{Component, ComponentLink, Html, ShouldRender};
+use yew::{ClickEvent, Component, ComponentLink, Html, ShouldRender};
 
 use yewtil::dsl::{list, populated_list, tag, text, BoxedVNodeProducer};
 
-pub struct Model {}
+pub struct Model {
+    link: ComponentLink<Self>,
+}
 
 pub enum Msg {
     DoIt,
@@ -12,8 +14,8 @@ impl Component for Model {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
-        Model {}
+    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Model { link }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
@@ -33,7 +35,12 @@ impl Component for Model {
                 .child(populated_list(vec![
                     tag("b").child(text("Bolded")).into(),
                     text("Normal text").into(),
-                ])),
+                ]))
+                .child(
+                    tag("button")
+                        .listener("click", self.link.callback(|_: ClickEvent| Msg::DoIt))
+                        .child(text("Do it")),
+                ),
         )
         .build()
     }
@@ -3,8 +3,10 @@ use yewtil::{function_component};
 
 #[function_component(Button)]
 pub fn button(
+    /// Called when the button is clicked.
     #[props(required)]
     callback: &Callback<ClickEvent>,
+    /// The button's label.
     text: &String,
     _num: usize
 ) -> Html {
@@ -0,0 +1,22 @@
+use yew::{html, Children, Html, Properties};
+use yewtil::{Pure, PureComponent};
+
+/// Alias to make usability better.
+pub type Card = Pure<PureCard>;
+
+#[derive(PartialEq, Properties)]
+pub struct PureCard {
+    pub title: String,
+    pub children: Children,
+}
+
+impl PureComponent for PureCard {
+    fn render(&self) -> Html {
+        html! {
+            <div class="card">
+                <h2>{ &self.title }</h2>
+                { self.children.iter().collect::<Html>() }
+            </div>
+        }
+    }
+}
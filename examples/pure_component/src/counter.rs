@@ -0,0 +1,28 @@
+use yew::{html, Html, Properties};
+use yewtil::ptr::Weak;
+use yewtil::{Pure, PureComponent};
+
+/// Alias to make usability better.
+pub type Counter = Pure<PureCounter>;
+
+/// Displays a count that is owned, and incremented in place, by the parent.
+#[derive(PartialEq, Properties)]
+pub struct PureCounter {
+    #[props(required)]
+    pub count: Weak<u32>,
+}
+
+impl PureComponent for PureCounter {
+    fn render(&self) -> Html {
+        let count = self.count.upgrade().map(|count| *count).unwrap_or_default();
+        html! {
+            <p>{ format!("Clicked {} times", count) }</p>
+        }
+    }
+
+    /// The `Weak` pointer doesn't change when the count is incremented,
+    /// so the count's generation is used to re-render the counter.
+    fn generation(&self) -> u64 {
+        self.count.generation()
+    }
+}
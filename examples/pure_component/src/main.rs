@@ -1,9 +1,17 @@
 use yew::{html, Component, ComponentLink, Html, ShouldRender};
+use yewtil::ptr::Mrc;
 
 mod button;
+mod card;
+mod counter;
 use crate::button::Button;
+use crate::card::Card;
+use crate::counter::Counter;
 
-pub struct Model {link: ComponentLink<Self>}
+pub struct Model {
+    link: ComponentLink<Self>,
+    count: Mrc<u32>,
+}
 
 pub enum Msg {
     DoIt,
@@ -14,13 +22,18 @@ impl Component for Model {
     type Properties = ();
 
     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
-        Model {link}
+        Model {
+            link,
+            count: Mrc::new(0),
+        }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
             Msg::DoIt => {
                 log::info!("got message");
+                // The counter only holds a `Weak` pointer, so the count is incremented in place.
+                *self.count += 1;
                 true
             }
         }
@@ -28,7 +41,10 @@ impl Component for Model {
 
     fn view(&self) -> Html {
         html! {
-            <Button callback=self.link.callback(|_| Msg::DoIt) text = "Click me!" />
+            <Card title="Pure components">
+                <Button callback=self.link.callback(|_| Msg::DoIt) text = "Click me!" />
+                <Counter count=self.count.downgrade() />
+            </Card>
         }
     }
 }
@@ -2,58 +2,135 @@ pub use crate::dsl::vcomp::VCompProducer;
 use crate::dsl::vlist::VListProducer;
 pub use crate::dsl::vtag::VTagProducer;
 pub use crate::dsl::vtext::VTextProducer;
-use yew::virtual_dom::vcomp::ScopeHolder;
 use yew::virtual_dom::VNode;
 use yew::Component;
 
+mod listener;
 mod vcomp;
 mod vlist;
 mod vtag;
 mod vtext;
 
 /// Wrapper around a function that produces a vnode.
-pub struct BoxedVNodeProducer<COMP: Component>(Box<dyn FnOnce(ScopeHolder<COMP>) -> VNode<COMP>>);
+pub struct BoxedVNodeProducer(Box<dyn FnOnce() -> VNode>);
 
-impl<COMP: Component> BoxedVNodeProducer<COMP> {
-    fn wrap(f: impl FnOnce(ScopeHolder<COMP>) -> VNode<COMP> + 'static) -> Self {
+impl BoxedVNodeProducer {
+    fn wrap(f: impl FnOnce() -> VNode + 'static) -> Self {
         BoxedVNodeProducer(Box::new(f))
     }
-    fn execute(self, scope: &ScopeHolder<COMP>) -> VNode<COMP> {
-        (self.0)(scope.clone())
+    fn execute(self) -> VNode {
+        (self.0)()
     }
-    pub fn build(self) -> VNode<COMP> {
-        let scope = ScopeHolder::default();
-        self.execute(&scope)
+    pub fn build(self) -> VNode {
+        self.execute()
+    }
+
+    /// Sets the key of the produced node, allowing Yew to match it across renders when diffing.
+    ///
+    /// Nodes that don't support keys (text nodes) are left unchanged.
+    pub fn keyed(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        BoxedVNodeProducer::wrap(move || {
+            let mut vnode = self.execute();
+            match &mut vnode {
+                VNode::VTag(vtag) => vtag.key = Some(key),
+                VNode::VComp(vcomp) => vcomp.key = Some(key),
+                VNode::VList(vlist) => vlist.key = Some(key),
+                _ => {}
+            }
+            vnode
+        })
     }
 }
 
-impl<COMP: Component> Into<VNode<COMP>> for BoxedVNodeProducer<COMP> {
-    fn into(self) -> VNode<COMP> {
-        self.build()
+impl From<BoxedVNodeProducer> for VNode {
+    fn from(producer: BoxedVNodeProducer) -> Self {
+        producer.build()
     }
 }
 
 /// Creates a tag node.
-pub fn tag<COMP: Component>(tag: &'static str) -> VTagProducer<COMP> {
+pub fn tag(tag: &'static str) -> VTagProducer {
     VTagProducer::new(tag)
 }
 
-/// Creates a component (Specified by the second type parameter).
-pub fn comp<COMP: Component, CHILD: Component>(props: CHILD::Properties) -> VCompProducer<COMP> {
-    VCompProducer::new::<CHILD>(props)
+/// Creates a component (Specified by the type parameter).
+pub fn comp<COMP: Component>(props: COMP::Properties) -> VCompProducer {
+    VCompProducer::new::<COMP>(props)
 }
 
 /// Creates a text node
-pub fn text<COMP: Component, T: Into<String> + 'static>(text: T) -> VTextProducer<COMP> {
+pub fn text<T: Into<String> + 'static>(text: T) -> VTextProducer {
     VTextProducer::new::<T>(text)
 }
 
 /// Creates a new vlist, populated with the provided vnodes
-pub fn populated_list<COMP: Component>(list: Vec<BoxedVNodeProducer<COMP>>) -> VListProducer<COMP> {
+pub fn populated_list(list: Vec<BoxedVNodeProducer>) -> VListProducer {
     VListProducer::populated_new(list)
 }
 
 /// Creates a new vlist
-pub fn list<COMP: Component>() -> VListProducer<COMP> {
+pub fn list() -> VListProducer {
     VListProducer::new()
 }
+
+/// Includes the node only if `condition` is true, producing an empty node otherwise.
+pub fn when<T: Into<BoxedVNodeProducer>>(condition: bool, producer: T) -> BoxedVNodeProducer {
+    if condition {
+        producer.into()
+    } else {
+        list().into()
+    }
+}
+
+/// Creates a new vlist containing a keyed node for every item.
+///
+/// Each node's key is produced by `key_fn`, and its content by `render_fn`.
+pub fn keyed_list<T, K, N>(
+    items: impl IntoIterator<Item = T>,
+    key_fn: impl Fn(&T) -> K,
+    render_fn: impl Fn(&T) -> N,
+) -> VListProducer
+where
+    K: Into<String>,
+    N: Into<BoxedVNodeProducer>,
+{
+    let children = items
+        .into_iter()
+        .map(|item| render_fn(&item).into().keyed(key_fn(&item)))
+        .collect();
+    VListProducer::populated_new(children)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keyed_list_keys_every_node() {
+        let items = vec![(1, "one"), (2, "two"), (3, "three")];
+        let list = keyed_list(
+            items,
+            |(id, _)| id.to_string(),
+            |(_, name)| tag("li").child(text(*name)),
+        );
+        let vnode = BoxedVNodeProducer::from(list).build();
+
+        let vlist = match vnode {
+            VNode::VList(vlist) => vlist,
+            other => panic!("Expected a list, got {:?}", other),
+        };
+        let keys: Vec<Option<String>> = vlist
+            .children
+            .iter()
+            .map(|child| match child {
+                VNode::VTag(vtag) => vtag.key.clone(),
+                other => panic!("Expected a tag, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![Some("1".to_string()), Some("2".to_string()), Some("3".to_string())]
+        );
+    }
+}
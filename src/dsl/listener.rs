@@ -0,0 +1,95 @@
+use std::any::{type_name, Any};
+use std::rc::Rc;
+use yew::virtual_dom::Listener;
+use yew::Callback;
+
+/// Generates `named_listener`, which wraps a callback in the listener for the named event.
+///
+/// Each listener only accepts a callback for its own event type,
+/// so the callback is downcast to the type expected by the event's wrapper.
+macro_rules! named_listeners {
+    ($($name:literal => $action:ident,)*) => {
+        /// Creates the listener for the event with the given name, eg. "click".
+        ///
+        /// If the event isn't known, or if the callback doesn't take that event's type,
+        /// the error is logged and `None` is returned.
+        pub(crate) fn named_listener<E: 'static>(event: &str, callback: Callback<E>) -> Option<Rc<dyn Listener>> {
+            let callback: Box<dyn Any> = Box::new(callback);
+            match event {
+                $(
+                    $name => match callback.downcast::<Callback<yew::html::$action::Event>>() {
+                        Ok(callback) => Some(Rc::new(yew::html::$action::Wrapper::new(*callback))),
+                        Err(_) => {
+                            log::error!(
+                                "The listener for \"{}\" expects a Callback<{}>, but a Callback<{}> was given",
+                                event,
+                                type_name::<yew::html::$action::Event>(),
+                                type_name::<E>()
+                            );
+                            None
+                        }
+                    },
+                )*
+                _ => {
+                    log::error!("Can't create a listener for the unknown event \"{}\"", event);
+                    None
+                }
+            }
+        }
+    };
+}
+
+// Every event that `yew::html` provides a listener for.
+named_listeners! {
+    "abort" => onabort,
+    "auxclick" => onauxclick,
+    "blur" => onblur,
+    "change" => onchange,
+    "click" => onclick,
+    "contextmenu" => oncontextmenu,
+    "dblclick" => ondoubleclick,
+    "drag" => ondrag,
+    "dragend" => ondragend,
+    "dragenter" => ondragenter,
+    "dragexit" => ondragexit,
+    "dragleave" => ondragleave,
+    "dragover" => ondragover,
+    "dragstart" => ondragstart,
+    "drop" => ondrop,
+    "error" => onerror,
+    "focus" => onfocus,
+    "gotpointercapture" => ongotpointercapture,
+    "input" => oninput,
+    "keydown" => onkeydown,
+    "keypress" => onkeypress,
+    "keyup" => onkeyup,
+    "load" => onload,
+    "loadend" => onloadend,
+    "loadstart" => onloadstart,
+    "lostpointercapture" => onlostpointercapture,
+    "mousedown" => onmousedown,
+    "mouseenter" => onmouseenter,
+    "mouseleave" => onmouseleave,
+    "mousemove" => onmousemove,
+    "mouseout" => onmouseout,
+    "mouseover" => onmouseover,
+    "mouseup" => onmouseup,
+    "pointercancel" => onpointercancel,
+    "pointerdown" => onpointerdown,
+    "pointerenter" => onpointerenter,
+    "pointerleave" => onpointerleave,
+    "pointermove" => onpointermove,
+    "pointerout" => onpointerout,
+    "pointerover" => onpointerover,
+    "pointerup" => onpointerup,
+    "progress" => onprogress,
+    "resize" => onresize,
+    "scroll" => onscroll,
+    "submit" => onsubmit,
+    "touchcancel" => ontouchcancel,
+    "touchend" => ontouchend,
+    "touchenter" => ontouchenter,
+    "touchmove" => ontouchmove,
+    "touchstart" => ontouchstart,
+    "wheel" => onmousewheel,
+}
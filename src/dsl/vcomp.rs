@@ -1,19 +1,63 @@
 use crate::dsl::BoxedVNodeProducer;
-use yew::virtual_dom::vcomp::ScopeHolder;
 use yew::virtual_dom::VComp;
 use yew::{Component, NodeRef};
 
-pub struct VCompProducer<COMP: Component>(Box<dyn FnOnce(ScopeHolder<COMP>) -> VComp<COMP>>);
+pub struct VCompProducer(Box<dyn FnOnce() -> VComp>);
 
-impl<COMP: Component> VCompProducer<COMP> {
-    pub fn new<CHILD: Component>(props: CHILD::Properties) -> Self {
+impl VCompProducer {
+    pub fn new<COMP: Component>(props: COMP::Properties) -> Self {
         // TODO allow getting the noderef as a parameter somewhere.
-        VCompProducer(Box::new(move |scope| VComp::new::<CHILD>(props, scope, NodeRef::default())))
+        VCompProducer(Box::new(move || VComp::new::<COMP>(props, NodeRef::default(), None)))
+    }
+
+    /// Sets the key of the component, allowing Yew to match it across renders when diffing.
+    pub fn key(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        VCompProducer(Box::new(move || {
+            let mut vcomp = (self.0)();
+            vcomp.key = Some(key);
+            vcomp
+        }))
+    }
+}
+
+impl From<VCompProducer> for BoxedVNodeProducer {
+    fn from(vcomp_prod: VCompProducer) -> Self {
+        BoxedVNodeProducer::wrap(move || (vcomp_prod.0)().into())
     }
 }
 
-impl<COMP: Component> From<VCompProducer<COMP>> for BoxedVNodeProducer<COMP> {
-    fn from(vcomp_prod: VCompProducer<COMP>) -> Self {
-        BoxedVNodeProducer::wrap(move |scope| (vcomp_prod.0)(scope).into())
+#[cfg(test)]
+mod test {
+    use super::*;
+    use yew::virtual_dom::VNode;
+    use yew::{html, ComponentLink, Html, ShouldRender};
+
+    struct Empty;
+
+    impl Component for Empty {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+            Empty
+        }
+
+        fn update(&mut self, _: Self::Message) -> ShouldRender {
+            false
+        }
+
+        fn view(&self) -> Html {
+            html! {}
+        }
+    }
+
+    #[test]
+    fn key_is_set_on_the_component() {
+        let producer = VCompProducer::new::<Empty>(()).key("first");
+        match BoxedVNodeProducer::from(producer).build() {
+            VNode::VComp(vcomp) => assert_eq!(vcomp.key, Some("first".to_string())),
+            other => panic!("Expected a component, got {:?}", other),
+        }
     }
 }
@@ -1,35 +1,76 @@
 use crate::dsl::BoxedVNodeProducer;
 use yew::virtual_dom::VList;
-use yew::Component;
 
-pub struct VListProducer<COMP: Component> {
-    children: Vec<BoxedVNodeProducer<COMP>>,
+pub struct VListProducer {
+    children: Vec<BoxedVNodeProducer>,
 }
 
-impl<COMP: Component> VListProducer<COMP> {
+impl VListProducer {
     pub fn new() -> Self {
         VListProducer { children: vec![] }
     }
 
-    pub fn child<T: Into<BoxedVNodeProducer<COMP>>>(mut self, child: T) -> Self {
+    pub fn child<T: Into<BoxedVNodeProducer>>(mut self, child: T) -> Self {
         self.children.push(child.into());
         self
     }
 
-    pub fn populated_new(children: Vec<BoxedVNodeProducer<COMP>>) -> Self {
+    /// Adds every node produced by the iterator as a child.
+    pub fn children<T: Into<BoxedVNodeProducer>>(
+        mut self,
+        children: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn populated_new(children: Vec<BoxedVNodeProducer>) -> Self {
         VListProducer { children }
     }
 }
 
-impl<COMP: Component> From<VListProducer<COMP>> for BoxedVNodeProducer<COMP> {
-    fn from(vlist_prod: VListProducer<COMP>) -> Self {
-        BoxedVNodeProducer(Box::new(move |scope| {
+impl Default for VListProducer {
+    fn default() -> Self {
+        VListProducer::new()
+    }
+}
+
+impl From<VListProducer> for BoxedVNodeProducer {
+    fn from(vlist_prod: VListProducer) -> Self {
+        BoxedVNodeProducer::wrap(move || {
             let mut vlist = VList::new();
             for child in vlist_prod.children {
-                let child = child.execute(&scope);
-                vlist.add_child(child);
+                vlist.add_child(child.execute());
             }
             vlist.into()
-        }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dsl::text;
+    use yew::virtual_dom::VNode;
+
+    #[test]
+    fn children_are_added_after_existing_children() {
+        let producer = VListProducer::new()
+            .child(text("first"))
+            .children(vec!["second", "third"].into_iter().map(text));
+
+        let vlist = match BoxedVNodeProducer::from(producer).build() {
+            VNode::VList(vlist) => vlist,
+            other => panic!("Expected a list, got {:?}", other),
+        };
+        let texts: Vec<&str> = vlist
+            .children
+            .iter()
+            .map(|child| match child {
+                VNode::VText(vtext) => vtext.text.as_str(),
+                other => panic!("Expected text, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(texts, vec!["first", "second", "third"]);
     }
 }
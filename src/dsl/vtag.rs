@@ -1,21 +1,22 @@
+use crate::dsl::listener::named_listener;
 use crate::dsl::BoxedVNodeProducer;
-use yew::virtual_dom::vcomp::ScopeHolder;
+use std::rc::Rc;
 use yew::virtual_dom::{Listener, VTag};
-use yew::{Classes, Component};
+use yew::{Callback, Classes};
 
-pub struct Effect<T, COMP: Component>(Box<dyn FnOnce(T, &ScopeHolder<COMP>) -> T>);
-impl<T, COMP: Component> Effect<T, COMP> {
-    fn new(f: impl FnOnce(T, &ScopeHolder<COMP>) -> T + 'static) -> Self {
+pub struct Effect<T>(Box<dyn FnOnce(T) -> T>);
+impl<T> Effect<T> {
+    fn new(f: impl FnOnce(T) -> T + 'static) -> Self {
         Effect(Box::new(f))
     }
 }
 
-pub struct VTagProducer<COMP: Component> {
+pub struct VTagProducer {
     tag_type: &'static str,
-    effects: Vec<Effect<VTag<COMP>, COMP>>,
+    effects: Vec<Effect<VTag>>,
 }
 
-impl<COMP: Component> VTagProducer<COMP> {
+impl VTagProducer {
     pub fn new(tag_type: &'static str) -> Self {
         VTagProducer {
             tag_type,
@@ -24,9 +25,9 @@ impl<COMP: Component> VTagProducer<COMP> {
     }
 
     // TODO, consider making this T: Into<VNode> - The whole dsl doesn't need to be lazy. - although being generic over an additional argument that is either () OR Scope is problematic.
-    pub fn child<T: Into<BoxedVNodeProducer<COMP>> + 'static>(mut self, child: T) -> Self {
-        let effect = Effect::new(move |mut vtag: VTag<COMP>, scope: &ScopeHolder<COMP>| {
-            let child = child.into().execute(scope);
+    pub fn child<T: Into<BoxedVNodeProducer> + 'static>(mut self, child: T) -> Self {
+        let effect = Effect::new(move |mut vtag: VTag| {
+            let child = child.into().execute();
             vtag.add_child(child);
             vtag
         });
@@ -34,8 +35,22 @@ impl<COMP: Component> VTagProducer<COMP> {
         self
     }
 
-    pub fn attribute(mut self, name: String, value: String) -> Self {
-        let effect = Effect::new(move |mut vtag: VTag<COMP>, _scope: &ScopeHolder<COMP>| {
+    /// Sets the key of the tag, allowing Yew to match it across renders when diffing.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        let effect = Effect::new(move |mut vtag: VTag| {
+            vtag.key = Some(key);
+            vtag
+        });
+        self.effects.push(effect);
+        self
+    }
+
+    /// Sets an attribute on the tag.
+    pub fn attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let value = value.into();
+        let effect = Effect::new(move |mut vtag: VTag| {
             vtag.add_attribute(&name, &value);
             vtag
         });
@@ -43,8 +58,29 @@ impl<COMP: Component> VTagProducer<COMP> {
         self
     }
 
-    pub fn listener(mut self, listener: Box<dyn Listener<COMP>>) -> Self {
-        let effect = Effect::new(move |mut vtag: VTag<COMP>, _scope: &ScopeHolder<COMP>| {
+    /// Adds a listener for the named event, eg. "click", to the tag.
+    ///
+    /// Every event that Yew's `html` module provides a listener for is supported,
+    /// and the callback must take that event's type, eg. `Callback<ClickEvent>` for "click".
+    /// If the event isn't supported, or the callback takes a different type,
+    /// an error is logged when the tag is built, and the listener is skipped.
+    pub fn listener<E: 'static>(mut self, event: &'static str, callback: Callback<E>) -> Self {
+        let effect = Effect::new(move |mut vtag: VTag| {
+            if let Some(listener) = named_listener(event, callback) {
+                vtag.add_listener(listener);
+            }
+            vtag
+        });
+        self.effects.push(effect);
+        self
+    }
+
+    /// Adds an already constructed event listener to the tag.
+    ///
+    /// The event is chosen by the wrapper that the callback is placed in,
+    /// eg. `Rc::new(onclick::Wrapper::new(callback))` for "click".
+    pub fn raw_listener(mut self, listener: Rc<dyn Listener>) -> Self {
+        let effect = Effect::new(move |mut vtag: VTag| {
             vtag.add_listener(listener);
             vtag
         });
@@ -52,8 +88,20 @@ impl<COMP: Component> VTagProducer<COMP> {
         self
     }
 
+    /// Adds a single class to the tag, keeping any classes that were already set.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        let effect = Effect::new(move |mut vtag: VTag| {
+            vtag.add_class(&class);
+            vtag
+        });
+        self.effects.push(effect);
+        self
+    }
+
+    /// Replaces the classes of the tag.
     pub fn classes(mut self, classes: Classes) -> Self {
-        let effect = Effect::new(move |mut vtag: VTag<COMP>, _scope: &ScopeHolder<COMP>| {
+        let effect = Effect::new(move |mut vtag: VTag| {
             vtag.set_classes(classes);
             vtag
         });
@@ -62,14 +110,69 @@ impl<COMP: Component> VTagProducer<COMP> {
     }
 }
 
-impl<COMP: Component> From<VTagProducer<COMP>> for BoxedVNodeProducer<COMP> {
-    fn from(vtag_prod: VTagProducer<COMP>) -> Self {
-        BoxedVNodeProducer::wrap(move |scope| {
+impl From<VTagProducer> for BoxedVNodeProducer {
+    fn from(vtag_prod: VTagProducer) -> Self {
+        BoxedVNodeProducer::wrap(move || {
             let mut vtag = VTag::new(vtag_prod.tag_type);
             for effect in vtag_prod.effects.into_iter() {
-                vtag = (effect.0)(vtag, &scope)
+                vtag = (effect.0)(vtag)
             }
             vtag.into()
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use yew::virtual_dom::VNode;
+    use yew::ClickEvent;
+
+    fn build_tag(producer: VTagProducer) -> VTag {
+        match BoxedVNodeProducer::from(producer).build() {
+            VNode::VTag(vtag) => *vtag,
+            other => panic!("Expected a tag, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn listener_and_class_are_set_on_the_tag() {
+        let callback = Callback::from(|_: ClickEvent| {});
+        let vtag = build_tag(
+            VTagProducer::new("button")
+                .listener("click", callback)
+                .class("primary")
+                .attribute("type", "submit"),
+        );
+
+        assert_eq!(vtag.listeners.len(), 1);
+        assert_eq!(vtag.listeners[0].kind(), "click");
+        assert!(vtag.classes.contains("primary"));
+        assert_eq!(vtag.attributes.get("type").map(String::as_str), Some("submit"));
+    }
+
+    #[test]
+    fn key_is_set_on_the_tag() {
+        let vtag = build_tag(VTagProducer::new("li").key("first"));
+        assert_eq!(vtag.key, Some("first".to_string()));
+    }
+
+    #[test]
+    fn listener_with_the_wrong_event_type_is_skipped() {
+        let callback = Callback::from(|_: String| {});
+        let vtag = build_tag(VTagProducer::new("button").listener("click", callback));
+        assert!(vtag.listeners.is_empty());
+    }
+
+    #[test]
+    fn listener_for_an_unknown_event_is_skipped() {
+        let callback = Callback::from(|_: ClickEvent| {});
+        let vtag = build_tag(
+            VTagProducer::new("button")
+                .listener("clik", callback.clone())
+                .listener("click", callback),
+        );
+        assert_eq!(vtag.listeners.len(), 1);
+        assert_eq!(vtag.listeners[0].kind(), "click");
+    }
+}
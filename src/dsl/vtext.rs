@@ -1,17 +1,16 @@
 use crate::dsl::BoxedVNodeProducer;
 use yew::virtual_dom::VText;
-use yew::Component;
 
-pub struct VTextProducer<COMP: Component>(Box<dyn FnOnce() -> VText<COMP>>);
+pub struct VTextProducer(Box<dyn FnOnce() -> VText>);
 
-impl<COMP: Component> VTextProducer<COMP> {
+impl VTextProducer {
     pub fn new<T: Into<String> + 'static>(text: T) -> Self {
         VTextProducer(Box::new(move || VText::new(text.into())))
     }
 }
 
-impl<COMP: Component> From<VTextProducer<COMP>> for BoxedVNodeProducer<COMP> {
-    fn from(vtext_prod: VTextProducer<COMP>) -> Self {
-        BoxedVNodeProducer::wrap(move |_scope| (vtext_prod.0)().into())
+impl From<VTextProducer> for BoxedVNodeProducer {
+    fn from(vtext_prod: VTextProducer) -> Self {
+        BoxedVNodeProducer::wrap(move || (vtext_prod.0)().into())
     }
 }
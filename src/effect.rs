@@ -1,5 +1,9 @@
-use yew::ShouldRender;
+use yew::{Component, ComponentLink, Html, Properties, ShouldRender};
 use std::rc::Rc;
+#[cfg(feature = "future")]
+use crate::future::LinkFuture;
+#[cfg(feature = "future")]
+use std::{cell::RefCell, future::Future};
 
 /// Alternative to using Message enums.
 ///
@@ -9,7 +13,7 @@ pub struct Effect<COMP>(Box<dyn Fn(&mut COMP) -> ShouldRender>);
 
 impl <COMP> Default for Effect<COMP> {
     fn default() -> Self {
-        Effect::new(|_| false)
+        Effect::noop()
     }
 }
 
@@ -19,10 +23,53 @@ impl <COMP> Effect<COMP> {
         Effect(Box::new(f))
     }
 
+    /// Creates an effect that doesn't change anything, and doesn't cause a re-render.
+    pub fn noop() -> Self {
+        Effect::new(|_| false)
+    }
+
     /// Runs the effect, causing a mutation to the component state.
     pub fn call(self, component: &mut COMP) -> ShouldRender {
         (self.0)(component)
     }
+
+    /// Combines this effect with another one that runs after it.
+    ///
+    /// Both effects are always run, and the component will re-render if either of them requests it.
+    pub fn and_then(self, next: Effect<COMP>) -> Self
+    where
+        COMP: 'static,
+    {
+        Effect::new(move |component| {
+            let first = (self.0)(component);
+            let second = (next.0)(component);
+            first || second
+        })
+    }
+}
+
+#[cfg(feature = "future")]
+impl<COMP: Component<Message = Effect<COMP>>> Effect<COMP> {
+    /// Creates an effect that runs a future, applying the effect it resolves to once it completes.
+    ///
+    /// The future is scheduled using `LinkFuture` when the effect is first called,
+    /// so the effect itself doesn't cause a re-render.
+    /// This allows async operations like fetching data to update the component without a `Message` enum.
+    ///
+    /// The component's `Message` must be `Effect<Self>` for the resolved effect to be sent back to it.
+    pub fn from_future<F>(link: &ComponentLink<COMP>, future: F) -> Self
+    where
+        F: Future<Output = Effect<COMP>> + 'static,
+    {
+        let link = link.clone();
+        let future = RefCell::new(Some(future));
+        Effect::new(move |_| {
+            if let Some(future) = future.borrow_mut().take() {
+                link.send_future(future);
+            }
+            false
+        })
+    }
 }
 
 /// Terser wrapper function to be used instead of `Effect::new()`.
@@ -31,6 +78,136 @@ pub fn effect<COMP>(f: impl Fn(&mut COMP) -> ShouldRender + 'static ) -> Effect<
     Effect::new(f)
 }
 
+/// A component whose state is changed by `Effect`s instead of messages.
+///
+/// This mirrors `Component`, except that it has no `update` method, because the
+/// effects sent to its link are applied directly to it.
+pub trait EffectfulComponent: Sized + 'static {
+    /// Properties of the component.
+    type Properties: Properties;
+
+    /// Creates the component.
+    ///
+    /// Effects can be sent to the component by creating callbacks with the provided link.
+    fn create(props: Self::Properties, link: ComponentLink<EffectComponent<Self>>) -> Self;
+
+    /// Called after the component has been mounted to the DOM.
+    fn mounted(&mut self) -> ShouldRender {
+        false
+    }
+
+    /// Called when the component receives new properties.
+    fn change(&mut self, props: Self::Properties) -> ShouldRender;
+
+    /// Renders the component to `Html`.
+    fn view(&self) -> Html;
+
+    /// Called before the component is destroyed.
+    fn destroy(&mut self) {}
+}
+
+/// Wrapper component for components that are updated using `Effect`s.
+///
+/// Its `update` applies every effect it receives to the wrapped component,
+/// so handlers can emit effects without an `update` function needing to be written.
+///
+/// # Example
+/// ```
+/// use yew::{ComponentLink, Html, Properties, ShouldRender};
+/// use yewtil::{EffectComponent, EffectfulComponent};
+///
+/// #[derive(Properties)]
+/// pub struct CounterProps {
+///     pub initial: usize,
+/// }
+///
+/// pub struct CounterImpl {
+///     count: usize,
+///     link: ComponentLink<Counter>,
+/// }
+///
+/// impl EffectfulComponent for CounterImpl {
+///     type Properties = CounterProps;
+///
+///     fn create(props: Self::Properties, link: ComponentLink<Counter>) -> Self {
+///         CounterImpl { count: props.initial, link }
+///     }
+///
+///     fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+///         false
+///     }
+///
+///     fn view(&self) -> Html {
+///#        unimplemented!()
+///         // eg. a button with `onclick=self.link.callback(|_| effect(|c: &mut CounterImpl| { c.count += 1; true }))`
+///     }
+/// }
+///
+/// /// Use this from within `html!` macros.
+/// pub type Counter = EffectComponent<CounterImpl>;
+/// ```
+#[derive(Debug)]
+pub struct EffectComponent<T> {
+    inner: T,
+}
+
+impl<T: EffectfulComponent> Component for EffectComponent<T> {
+    type Message = Effect<T>;
+    type Properties = T::Properties;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        EffectComponent {
+            inner: T::create(props, link),
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        self.inner.mounted()
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        msg.call(&mut self.inner)
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.inner.change(props)
+    }
+
+    fn view(&self) -> Html {
+        self.inner.view()
+    }
+
+    fn destroy(&mut self) {
+        self.inner.destroy()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn and_then_runs_both_effects_in_order() {
+        let mut values: Vec<usize> = vec![];
+        let add_one = Effect::new(|values: &mut Vec<usize>| {
+            values.push(1);
+            false
+        });
+        let add_two = Effect::new(|values: &mut Vec<usize>| {
+            values.push(2);
+            true
+        });
+        let should_render = add_one.and_then(add_two).call(&mut values);
+        assert!(should_render);
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    fn noop_effects_do_not_render() {
+        let effect: Effect<()> = Effect::noop().and_then(Effect::default());
+        assert!(!effect.call(&mut ()));
+    }
+}
 
 #[allow(dead_code)]
 mod wip {
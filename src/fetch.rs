@@ -5,21 +5,76 @@
 //! # Note
 //! Because this makes use of futures, enabling this feature will require the use of a
 //! web_sys compatible build environment and will prevent you from using `cargo web`.
+//!
+//! Functions that make requests using the browser, like `fetch_head`, are only available when
+//! targeting wasm. The state types (`Fetch`, `FetchState`, `FetchAction`, `FetchError`) and the
+//! `FetchRequest` trait are available on every target, so `update` logic that manipulates them can be
+//! unit tested on the host. `fetch_request` and `Fetch::fetch` are available on every target as well,
+//! and can be pointed at a `MockBackend` using `set_fetch_backend` to test components that make requests.
 
 
 use crate::NeqAssign; // requires "neq" feature.
+#[cfg(feature = "mrc_irc")]
+use crate::ptr::Irc;
 
 mod action;
 mod error;
 mod state;
 mod request;
+mod response;
+mod header_store;
+mod auto_fetch;
+#[cfg(feature = "csv")]
+mod csv_format;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "urlencoded")]
+mod url_encoded;
+
+// Making requests requires a browser, so it is only supported when targeting wasm.
+// The modules are still compiled for this crate's own tests, as web-sys builds on every target.
+#[cfg(any(target_arch = "wasm32", test))]
+mod client;
+#[cfg(any(target_arch = "wasm32", test))]
+mod poll;
+#[cfg(any(target_arch = "wasm32", test))]
+mod abort;
+#[cfg(any(target_arch = "wasm32", test))]
+mod timeout;
+#[cfg(any(target_arch = "wasm32", test))]
+mod retry;
+#[cfg(any(target_arch = "wasm32", test))]
+mod progress;
+mod query;
+mod backend;
 
 pub use self::action::*;
 pub use self::error::*;
 pub use self::state::*;
 pub use self::request::*;
-use wasm_bindgen::__rt::core::marker::PhantomData;
+pub use yewtil_macro::FetchRequest;
+pub use self::response::*;
+pub use self::header_store::*;
+pub use self::auto_fetch::*;
+pub use self::backend::*;
+#[cfg(feature = "csv")]
+pub use self::csv_format::*;
+#[cfg(feature = "msgpack")]
+pub use self::msgpack::*;
+#[cfg(feature = "urlencoded")]
+pub use self::url_encoded::*;
+#[cfg(any(target_arch = "wasm32", test))]
+pub use self::client::*;
+#[cfg(any(target_arch = "wasm32", test))]
+pub use self::poll::*;
+#[cfg(any(target_arch = "wasm32", test))]
+pub use self::abort::*;
+#[cfg(any(target_arch = "wasm32", test))]
+pub use self::retry::*;
+#[cfg(any(target_arch = "wasm32", test))]
+pub use self::progress::*;
 use std::future::Future;
+use std::time::Duration;
 
 /// Indicates that a change was caused by a set function.
 pub type DidChange = bool;
@@ -38,10 +93,52 @@ pub type AcquireFetch<T> = Fetch<(), T>;
 pub type ModifyFetch<T> = Fetch<T, T>;
 
 
-#[derive(Clone, Debug, PartialEq, Default)]
-pub struct Fetch<REQ, RES> {
+/// Wrapper that holds a request and the state of fetching its response.
+///
+/// The error type is only changed from `FetchError` by `as_ref` and `as_mut`,
+/// which borrow the error instead of cloning it.
+///
+/// Two wrappers are equal if their requests and states are equal,
+/// regardless of when their requests began.
+#[derive(Clone, Debug)]
+pub struct Fetch<REQ, RES, ERR = FetchError> {
     request: REQ,
-    response: FetchState<RES>
+    response: FetchState<RES, ERR>,
+    /// The time, in milliseconds since the epoch, at which the in-flight request began.
+    fetching_since: Option<f64>,
+}
+
+impl <REQ: Default, RES, ERR> Default for Fetch<REQ, RES, ERR> {
+    fn default() -> Self {
+        Fetch {
+            request: REQ::default(),
+            response: FetchState::default(),
+            fetching_since: None,
+        }
+    }
+}
+
+impl <REQ: PartialEq, RES: PartialEq, ERR: PartialEq> PartialEq for Fetch<REQ, RES, ERR> {
+    fn eq(&self, other: &Self) -> bool {
+        self.request == other.request && self.response == other.response
+    }
+}
+
+/// Gets the current time in milliseconds since the epoch.
+///
+/// `Instant` isn't supported on `wasm32-unknown-unknown`, so the browser's clock is used there instead.
+#[cfg(target_arch = "wasm32")]
+fn now_millis() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Gets the current time in milliseconds since the epoch.
+#[cfg(not(target_arch = "wasm32"))]
+fn now_millis() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
 }
 
 
@@ -68,6 +165,7 @@ impl <REQ: Default, RES: PartialEq> Fetch<REQ, RES> {
         let old = std::mem::replace(&mut self.response, FetchState::default());
         let new = old.fetched(res);
         std::mem::replace(&mut self.response, new);
+        self.fetching_since = None;
 
         will_change
     }
@@ -77,8 +175,9 @@ impl <REQ: Default, RES: PartialEq> Fetch<REQ, RES> {
         match action {
             FetchAction::NotFetching => self.set_not_fetching(),
             FetchAction::Fetching => self.set_fetching(),
-            FetchAction::Success(res) => self.set_fetched(res),
+            FetchAction::Fetched(res) => self.set_fetched(res),
             FetchAction::Failed(err) => self.set_failed(err),
+            FetchAction::Canceled => self.set_not_fetching(),
         }
     }
 }
@@ -90,7 +189,42 @@ impl <REQ, RES> Fetch<REQ, RES> {
     pub fn new(request: REQ) -> Self {
         Self {
             request,
-            response: Default::default()
+            response: Default::default(),
+            fetching_since: None,
+        }
+    }
+
+    /// Creates a new Fetch wrapper around a request whose response is already known.
+    ///
+    /// This is useful for hydrating components with data that was fetched ahead of time, and for tests.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::{Fetch, FetchState};
+    /// let fetch: Fetch<(), String> = Fetch::fetched((), "Response".to_string());
+    /// assert_eq!(fetch.state(), FetchState::Fetched("Response".to_string()));
+    /// ```
+    pub fn fetched(request: REQ, response: RES) -> Self {
+        Self {
+            request,
+            response: FetchState::Fetched(response),
+            fetching_since: None,
+        }
+    }
+
+    /// Creates a new Fetch wrapper around a request that is already known to have failed.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::{Fetch, FetchError, FetchState};
+    /// let fetch: Fetch<(), String> = Fetch::failed_with((), FetchError::Timeout);
+    /// assert_eq!(fetch.state(), FetchState::Failed(None, FetchError::Timeout));
+    /// ```
+    pub fn failed_with(request: REQ, err: FetchError) -> Self {
+        Self {
+            request,
+            response: FetchState::Failed(None, err),
+            fetching_since: None,
         }
     }
 
@@ -101,13 +235,19 @@ impl <REQ, RES> Fetch<REQ, RES> {
         let old = std::mem::replace(&mut self.response, FetchState::default());
         let new = old.not_fetching();
         std::mem::replace(&mut self.response, new);
+        self.fetching_since = None;
 
         will_change
     }
 
     /// Sets the response field to indicate that a fetch request is currently being made.
+    ///
+    /// If a request wasn't already in flight, the time is recorded for `should_show_loading`.
     pub fn set_fetching(&mut self) -> DidChange {
         let will_change = self.response.discriminant_differs(&FetchState::Fetching(None));
+        if !self.response.is_fetching() {
+            self.fetching_since = Some(now_millis());
+        }
 
         let old = std::mem::replace(&mut self.response, FetchState::default());
         let new = old.fetching();
@@ -116,6 +256,24 @@ impl <REQ, RES> Fetch<REQ, RES> {
         will_change
     }
 
+    /// Indicates that a request is about to be made, while keeping the last response around.
+    ///
+    /// This is the same as `set_fetching`, and exists to make it clear that the previous response
+    /// (if any) is preserved while the request is in flight, so it can keep being shown using `displayed`.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::Fetch;
+    /// let mut fetch: Fetch<(), String> = Fetch::new(());
+    /// fetch.set_fetched("Stale".to_string());
+    ///
+    /// assert!(fetch.begin_fetch());
+    /// assert_eq!(fetch.displayed(), Some(&"Stale".to_string()));
+    /// ```
+    pub fn begin_fetch(&mut self) -> DidChange {
+        self.set_fetching()
+    }
+
     /// Sets the response field to indicate that a fetch request failed to complete.
     pub fn set_failed(&mut self, err: FetchError) -> DidChange {
         let will_change = match &self.response {
@@ -128,6 +286,7 @@ impl <REQ, RES> Fetch<REQ, RES> {
         let old = std::mem::replace(&mut self.response, FetchState::default());
         let new = old.failed(err);
         std::mem::replace(&mut self.response, new);
+        self.fetching_since = None;
 
         will_change
     }
@@ -143,15 +302,9 @@ impl <REQ, RES> Fetch<REQ, RES> {
         to_msg: impl Fn(FetchAction<T::ResponseBody>) -> Msg
     ) -> impl Future<Output=Msg> {
         let request: &T = to_request(self);
-        let request = create_request(request);
-        let req_type: PhantomData<T> = PhantomData;
+        let fetch = fetch_request(request);
         async move {
-            let fetch_state = match fetch_resource(request, req_type).await {
-                Ok(response) => FetchAction::Success(response),
-                Err(err) => FetchAction::Failed(err)
-            };
-
-            to_msg(fetch_state)
+            to_msg(fetch.await.into())
         }
     }
 
@@ -160,6 +313,18 @@ impl <REQ, RES> Fetch<REQ, RES> {
         f(self)
     }
 
+    /// Moves the response (if any) into an `Irc`, so it can be cheaply shared with child components.
+    #[cfg(feature = "mrc_irc")]
+    pub fn into_shared(self) -> Fetch<REQ, Irc<RES>> {
+        Fetch {
+            request: self.request,
+            response: self.response.map(Irc::from),
+            fetching_since: self.fetching_since,
+        }
+    }
+}
+
+impl <REQ, RES, ERR> Fetch<REQ, RES, ERR> {
     /// Unwraps the Fetch wrapper to produce the response it may contain.
     ///
     /// # Panics
@@ -179,47 +344,85 @@ impl <REQ, RES> Fetch<REQ, RES> {
         }
     }
 
+    /// Gets a reference to the response that should currently be shown, if any.
+    ///
+    /// This is the most recent response, regardless of whether a request is in flight or has failed since.
+    pub fn displayed(&self) -> Option<&RES> {
+        match &self.response {
+            FetchState::NotFetching(res) => res.as_ref(),
+            FetchState::Fetching(res) => res.as_ref(),
+            FetchState::Fetched(res) => Some(res),
+            FetchState::Failed(res, _) => res.as_ref(),
+        }
+    }
+
     /// Gets the request body.
     pub fn req(self) -> REQ {
         self.request
     }
 
-    pub fn state(self) -> FetchState<RES> {
+    pub fn state(self) -> FetchState<RES, ERR> {
         self.response
     }
 
-    /// Converts the wrapped values to references.
+    /// Returns true if a request is in flight and has been for at least `delay`.
     ///
-    /// # Note
-    /// This may be expensive if a Failed variant made into a reference, as the FetchError is cloned.
-    pub fn as_ref(&self) -> Fetch<&REQ, &RES> {
+    /// Rendering the loading UI only once this is true keeps it from flickering for responses that arrive quickly.
+    /// The time is recorded when the wrapper enters the fetching state, using `set_fetching`, `begin_fetch`,
+    /// or by applying `FetchAction::Fetching`.
+    ///
+    /// Nothing re-renders the component when `delay` elapses, so it should schedule an update for then,
+    /// such as by sending a message from a timeout.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::Fetch;
+    ///# use std::time::Duration;
+    /// let mut fetch: Fetch<(), String> = Fetch::new(());
+    /// assert!(!fetch.should_show_loading(Duration::from_millis(0)));
+    ///
+    /// fetch.set_fetching();
+    /// assert!(fetch.should_show_loading(Duration::from_millis(0)));
+    /// assert!(!fetch.should_show_loading(Duration::from_secs(60)));
+    /// ```
+    pub fn should_show_loading(&self, delay: Duration) -> bool {
+        match self.fetching_since {
+            Some(since) => {
+                let fetching_for = Duration::from_millis((now_millis() - since).max(0.0) as u64);
+                self.response.should_show_loading(fetching_for, delay)
+            }
+            None => false,
+        }
+    }
+
+    /// Converts the wrapped values to references.
+    pub fn as_ref(&self) -> Fetch<&REQ, &RES, &ERR> {
         let response = match &self.response {
             FetchState::NotFetching(res) => FetchState::NotFetching(res.as_ref()),
             FetchState::Fetching(res) => FetchState::Fetching(res.as_ref()),
             FetchState::Fetched(res) => FetchState::Fetched(res),
-            FetchState::Failed(res, err) => FetchState::Failed(res.as_ref(), err.clone()),
+            FetchState::Failed(res, err) => FetchState::Failed(res.as_ref(), err),
         };
 
         Fetch {
             request: &self.request,
-            response
+            response,
+            fetching_since: self.fetching_since,
         }
     }
 
     /// Converts the wrapped values to mutable references.
-    ///
-    /// # Note
-    /// This may be expensive if a Failed variant made into a reference, as the FetchError is cloned.
-    pub fn as_mut(&mut self) -> Fetch<&mut REQ, &mut RES> {
+    pub fn as_mut(&mut self) -> Fetch<&mut REQ, &mut RES, &mut ERR> {
         let response = match &mut self.response {
             FetchState::NotFetching(res) => FetchState::NotFetching(res.as_mut()),
             FetchState::Fetching(res) => FetchState::Fetching(res.as_mut()),
             FetchState::Fetched(res) => FetchState::Fetched(res),
-            FetchState::Failed(res, err) => FetchState::Failed(res.as_mut(), err.clone()),
+            FetchState::Failed(res, err) => FetchState::Failed(res.as_mut(), err),
         };
         Fetch {
             request: &mut self.request,
-            response
+            response,
+            fetching_since: self.fetching_since,
         }
     }
 }
@@ -232,16 +435,9 @@ impl <REQ: FetchRequest> Fetch<REQ, REQ::ResponseBody>{
         &self,
         to_msg: impl Fn(FetchAction<REQ::ResponseBody>) -> Msg
     )-> impl Future<Output=Msg> {
-        let request = self.as_ref().req();
-        let request = create_request(request);
-        let req_type: PhantomData<REQ> = PhantomData;
+        let fetch = fetch_request(&self.request);
         async move {
-            let fetch_state = match fetch_resource(request, req_type).await {
-                Ok(response) => FetchAction::Success(response),
-                Err(err) => FetchAction::Failed(err)
-            };
-
-            to_msg(fetch_state)
+            to_msg(fetch.await.into())
         }
     }
 }
@@ -269,7 +465,8 @@ mod test {
     fn setting_fetched_state() {
         let mut fs = Fetch {
             request: (),
-            response: FetchState::Fetching(None)
+            response: FetchState::Fetching(None),
+            fetching_since: None,
         };
         assert!(fs.set_fetched("SomeValue".to_string()));
         assert_eq!(fs.response, FetchState::Fetched("SomeValue".to_string()));
@@ -279,9 +476,50 @@ mod test {
     fn setting_fetching_from_fetched() {
         let mut fs = Fetch {
             request: (),
-            response: FetchState::Fetched("Lorem".to_string())
+            response: FetchState::Fetched("Lorem".to_string()),
+            fetching_since: None,
         };
         assert!(fs.set_fetching());
         assert_eq!(fs.response, FetchState::Fetching(Some("Lorem".to_string())));
     }
+
+    #[test]
+    fn fetching_start_time_is_kept_until_the_request_ends() {
+        let mut fs: Fetch<(), String> = Fetch::new(());
+        fs.set_fetching();
+        let since = fs.fetching_since.expect("Start time should be recorded");
+
+        fs.set_fetching();
+        assert_eq!(fs.fetching_since, Some(since));
+        assert_eq!(fs, Fetch { request: (), response: FetchState::Fetching(None), fetching_since: None });
+
+        fs.set_fetched("Response".to_string());
+        assert_eq!(fs.fetching_since, None);
+        assert!(!fs.should_show_loading(Duration::from_millis(0)));
+    }
+
+    #[test]
+    fn as_ref_borrows_error() {
+        let fs: Fetch<(), String> = Fetch {
+            request: (),
+            response: FetchState::Failed(None, FetchError::TextNotAvailable),
+            fetching_since: None,
+        };
+        assert_eq!(fs.as_ref().state(), FetchState::Failed(None, &FetchError::TextNotAvailable));
+    }
+
+    #[cfg(feature = "mrc_irc")]
+    #[test]
+    fn into_shared_preserves_state() {
+        let fs = Fetch {
+            request: (),
+            response: FetchState::Failed(Some("Lorem".to_string()), FetchError::TextNotAvailable),
+            fetching_since: None,
+        };
+        let shared = fs.into_shared();
+        assert_eq!(
+            shared.response,
+            FetchState::Failed(Some(Irc::new("Lorem".to_string())), FetchError::TextNotAvailable)
+        );
+    }
 }
@@ -0,0 +1,162 @@
+use crate::fetch::{create_request_with_signal, fetch_request_with_signal, FetchAction, FetchError, FetchRequest};
+use std::future::Future;
+use web_sys::{AbortController, AbortSignal, Request};
+
+/// A handle that can be used to cancel fetch requests that are in flight.
+///
+/// It wraps an `AbortController`, whose signal is attached to every request made with it.
+/// Dropping the handle aborts any requests that are still in flight,
+/// so storing it in a component ensures that its requests don't outlive it.
+#[derive(Debug)]
+pub struct AbortHandle {
+    controller: AbortController,
+}
+
+impl AbortHandle {
+    /// Creates a new handle.
+    ///
+    /// # Panics
+    /// If the browser doesn't support `AbortController`, this will panic.
+    pub fn new() -> Self {
+        let controller = AbortController::new().expect("AbortController should be supported");
+        AbortHandle { controller }
+    }
+
+    /// Aborts every request made with this handle that hasn't completed yet.
+    pub fn abort(&self) {
+        self.controller.abort()
+    }
+
+    /// Returns true if `abort` has been called.
+    pub fn is_aborted(&self) -> bool {
+        self.signal().aborted()
+    }
+
+    /// Gets the signal that is attached to requests to make them abortable.
+    pub fn signal(&self) -> AbortSignal {
+        self.controller.signal()
+    }
+}
+
+impl Default for AbortHandle {
+    fn default() -> Self {
+        AbortHandle::new()
+    }
+}
+
+impl Drop for AbortHandle {
+    fn drop(&mut self) {
+        self.abort()
+    }
+}
+
+/// A group of fetch requests that can be cancelled together,
+/// such as every request made by a page that the user is navigating away from.
+///
+/// All requests made through the group share a single `AbortController`.
+/// Dropping the group aborts any of its requests that are still in flight.
+#[derive(Debug, Default)]
+pub struct FetchGroup {
+    handle: AbortHandle,
+}
+
+impl FetchGroup {
+    /// Creates a new, empty group.
+    ///
+    /// # Panics
+    /// If the browser doesn't support `AbortController`, this will panic.
+    pub fn new() -> Self {
+        FetchGroup {
+            handle: AbortHandle::new(),
+        }
+    }
+
+    /// Creates a request that belongs to this group.
+    ///
+    /// Prefer `fetch`, which also applies the request's `timeout` and uses the installed `FetchBackend`.
+    pub fn create_request<T: FetchRequest>(&self, request: &T) -> Result<Request, FetchError> {
+        create_request_with_signal(request, Some(&self.signal()))
+    }
+
+    /// Makes a fetch request that belongs to this group.
+    ///
+    /// If the group is aborted before the request completes, the future will resolve to `FetchError::Aborted`.
+    pub fn fetch<T: FetchRequest>(&self, request: &T) -> impl Future<Output = Result<T::ResponseBody, FetchError>> {
+        fetch_request_with_signal(request, Some(&self.signal()))
+    }
+
+    /// Makes a fetch request that belongs to this group,
+    /// which will produce a message that makes use of a `FetchAction` when it completes.
+    ///
+    /// Requests aborted by the group produce `FetchAction::Canceled`.
+    pub fn fetch_to_state_msg<T: FetchRequest, Msg>(
+        &self,
+        request: &T,
+        to_msg: impl Fn(FetchAction<T::ResponseBody>) -> Msg,
+    ) -> impl Future<Output = Msg> {
+        fetch_to_state_msg_abortable(request, &self.handle, to_msg)
+    }
+
+    /// Aborts every request in the group that hasn't completed yet.
+    ///
+    /// Requests made through the group after this is called are aborted immediately.
+    pub fn abort_all(&self) {
+        self.handle.abort()
+    }
+
+    /// Returns true if `abort_all` has been called.
+    pub fn is_aborted(&self) -> bool {
+        self.handle.is_aborted()
+    }
+
+    /// Gets the signal shared by every request in the group.
+    pub fn signal(&self) -> AbortSignal {
+        self.handle.signal()
+    }
+}
+
+/// Makes an asynchronous fetch request that can be cancelled using the `AbortHandle`,
+/// which will produce a message that makes use of a `FetchAction` when it completes.
+///
+/// If the request was aborted, the message will contain `FetchAction::Canceled` instead of
+/// `FetchAction::Failed`, which allows user-initiated cancellation to be told apart from real failures.
+pub fn fetch_to_state_msg_abortable<T: FetchRequest, Msg>(
+    request: &T,
+    handle: &AbortHandle,
+    to_msg: impl Fn(FetchAction<T::ResponseBody>) -> Msg,
+) -> impl Future<Output = Msg> {
+    let fetch = fetch_request_with_signal(request, Some(&handle.signal()));
+    async move {
+        let result = fetch.await;
+        to_msg(abortable_action(result))
+    }
+}
+
+/// Converts the result of an abortable request into an action.
+fn abortable_action<T>(result: Result<T, FetchError>) -> FetchAction<T> {
+    match result {
+        Ok(response) => FetchAction::Fetched(response),
+        Err(FetchError::Aborted) => FetchAction::Canceled,
+        Err(err) => FetchAction::Failed(err),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aborting_produces_canceled_action() {
+        let result: Result<(), FetchError> = Err(FetchError::Aborted);
+        assert_eq!(abortable_action(result), FetchAction::Canceled);
+    }
+
+    #[test]
+    fn failure_without_abort_produces_failed_action() {
+        let result: Result<(), FetchError> = Err(FetchError::CouldNotCreateFetchFuture);
+        assert_eq!(
+            abortable_action(result),
+            FetchAction::Failed(FetchError::CouldNotCreateFetchFuture)
+        );
+    }
+}
@@ -6,8 +6,11 @@ use crate::fetch::FetchError;
 pub enum FetchAction<T> {
     NotFetching,
     Fetching,
-    Success(T), // TODO rename to Fetched(T)
+    /// The request completed successfully.
+    Fetched(T),
     Failed(FetchError),
+    /// The request was aborted before it could complete.
+    Canceled,
 }
 
 impl <T> Default for FetchAction<T> {
@@ -16,18 +19,49 @@ impl <T> Default for FetchAction<T> {
     }
 }
 
+/// Converts the result of a request into the action that should be applied to a `Fetch` wrapper.
+impl <T> From<Result<T, FetchError>> for FetchAction<T> {
+    fn from(result: Result<T, FetchError>) -> Self {
+        match result {
+            Ok(value) => FetchAction::Fetched(value),
+            Err(err) => FetchAction::Failed(err),
+        }
+    }
+}
+
 impl <T> FetchAction<T> {
-    /// Returns a reference to the Success case
-    pub fn success(&self) -> Option<&T> {
+    /// Creates the `Fetched` case.
+    pub fn fetched(value: T) -> Self {
+        FetchAction::Fetched(value)
+    }
+
+    /// Creates the `Fetched` case, which used to be called `Success`.
+    ///
+    /// This only supports construction, so patterns matching on `Success` must be changed to `Fetched`.
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[deprecated(note = "The `Success` variant was renamed to `Fetched`")]
+    pub fn Success(value: T) -> Self {
+        FetchAction::Fetched(value)
+    }
+
+    /// Returns a reference to the Fetched case
+    pub fn as_fetched(&self) -> Option<&T> {
         match self {
-            FetchAction::Success(value) => Some(value),
+            FetchAction::Fetched(value) => Some(value),
             _ => None
         }
     }
 
-    /// Gets the value out of the fetch state if it is a `Success` variant.
+    /// Returns a reference to the Fetched case
+    #[deprecated(note = "Use `as_fetched` instead")]
+    pub fn success(&self) -> Option<&T> {
+        self.as_fetched()
+    }
+
+    /// Gets the value out of the fetch state if it is a `Fetched` variant.
     pub fn unwrap(self) -> T {
-        if let FetchAction::Success(value) = self {
+        if let FetchAction::Fetched(value) = self {
             value
         } else {
             panic!("Could not unwrap value of FetchState");
@@ -39,15 +73,62 @@ impl <T> FetchAction<T> {
         match self {
             FetchAction::NotFetching => FetchAction::NotFetching,
             FetchAction::Fetching => FetchAction::NotFetching,
-            FetchAction::Success(t) => FetchAction::Success(f(t)),
-            FetchAction::Failed(e) => FetchAction::Failed(e)
+            FetchAction::Fetched(t) => FetchAction::Fetched(f(t)),
+            FetchAction::Failed(e) => FetchAction::Failed(e),
+            FetchAction::Canceled => FetchAction::Canceled,
+        }
+    }
+
+    /// Transforms the `Fetched` case using a function that may itself produce any action,
+    /// such as `Failed` when the fetched value doesn't pass validation.
+    ///
+    /// The other cases are preserved.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::{FetchAction, FetchError};
+    /// let validate = |count: i32| if count >= 0 {
+    ///     FetchAction::Fetched(count as u32)
+    /// } else {
+    ///     FetchAction::Failed(FetchError::DeserializeError {
+    ///         error: "negative count".to_string(),
+    ///         content: count.to_string(),
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(FetchAction::Fetched(5).and_then(validate), FetchAction::Fetched(5));
+    /// assert!(matches!(FetchAction::Fetched(-1).and_then(validate), FetchAction::Failed(_)));
+    /// assert_eq!(FetchAction::Fetching.and_then(validate), FetchAction::Fetching);
+    /// ```
+    pub fn and_then<U, F: Fn(T) -> FetchAction<U>>(self, f: F) -> FetchAction<U> {
+        match self {
+            FetchAction::NotFetching => FetchAction::NotFetching,
+            FetchAction::Fetching => FetchAction::Fetching,
+            FetchAction::Fetched(t) => f(t),
+            FetchAction::Failed(e) => FetchAction::Failed(e),
+            FetchAction::Canceled => FetchAction::Canceled,
+        }
+    }
+
+    /// Gets the value out of the `Fetched` case, or returns `default` for any other case.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchAction;
+    /// assert_eq!(FetchAction::Fetched(5).unwrap_or(0), 5);
+    /// assert_eq!(FetchAction::Canceled.unwrap_or(0), 0);
+    /// ```
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            FetchAction::Fetched(value) => value,
+            _ => default,
         }
     }
 
     /// Applies a function that mutates the response if the Action is the success case.
     pub fn alter<F: Fn(&mut T)>(&mut self, f: F) {
         match self {
-            FetchAction::Success(t) => f(t),
+            FetchAction::Fetched(t) => f(t),
             _ => {}
         }
     }
@@ -57,8 +138,9 @@ impl <T> FetchAction<T> {
         match self {
             FetchAction::NotFetching => FetchAction::NotFetching,
             FetchAction::Fetching => FetchAction::NotFetching,
-            FetchAction::Success(t) => FetchAction::Success(t),
-            FetchAction::Failed(e) => FetchAction::Failed(e.clone())
+            FetchAction::Fetched(t) => FetchAction::Fetched(t),
+            FetchAction::Failed(e) => FetchAction::Failed(e.clone()),
+            FetchAction::Canceled => FetchAction::Canceled,
         }
     }
 }
@@ -0,0 +1,142 @@
+use crate::fetch::{fetch_request, FetchAction, FetchError, FetchRequest, FetchState};
+use crate::future::{LinkFuture, SpawnHandle};
+use std::rc::Rc;
+use yew::{html, Component, ComponentLink, Html, Properties, ShouldRender};
+
+/// Function that renders a part of the state of a fetch request.
+pub type Render<T> = Rc<dyn Fn(&T) -> Html>;
+
+/// Properties of the `AutoFetch` component.
+#[derive(Properties, Clone)]
+pub struct AutoFetchProps<REQ: FetchRequest + Clone + 'static> {
+    /// The request to make.
+    ///
+    /// It is made when the component is mounted, and again whenever it changes.
+    #[props(required)]
+    pub request: REQ,
+    /// Renders the most recent response.
+    ///
+    /// The previous response keeps being rendered while a changed request is in flight.
+    #[props(required)]
+    pub fetched: Render<REQ::ResponseBody>,
+    /// Rendered while a request is in flight and there isn't a previous response to show.
+    pub fetching: Option<Html>,
+    /// Renders the error if the most recent request failed.
+    ///
+    /// If this isn't provided, the previous response (if any) is rendered instead.
+    pub failed: Option<Render<FetchError>>,
+}
+
+/// Component that makes a fetch request described by its props, and renders its state.
+///
+/// This takes care of sending the request and handling its response,
+/// so the state of a request can be shown without any messages being written.
+/// Responses to requests that have since changed are discarded.
+///
+/// # Example
+/// ```
+///# use std::rc::Rc;
+///# use yew::{html, Html};
+///# use yewtil::fetch::{AutoFetch, FetchRequest, Json, MethodBody, Render};
+/// #[derive(Clone, PartialEq)]
+/// pub struct GetApple(usize);
+///
+/// impl FetchRequest for GetApple {
+///     type RequestBody = ();
+///     type ResponseBody = String;
+///     type Format = Json;
+///
+///     fn url(&self) -> String {
+///         format!("http://some_host_website.com/apples/{}", self.0)
+///     }
+///
+///     fn method(&self) -> MethodBody<Self::RequestBody> {
+///         MethodBody::Get
+///     }
+///
+///     fn headers(&self) -> Vec<(String, String)> {
+///         vec![]
+///     }
+/// }
+///
+/// fn view_apple(id: usize) -> Html {
+///     let render_name: Render<String> = Rc::new(|name| html! { <span>{ name }</span> });
+///     html! {
+///         <AutoFetch<GetApple> request=GetApple(id) fetched=render_name fetching=html! { "Loading" } />
+///     }
+/// }
+/// ```
+pub struct AutoFetch<REQ: FetchRequest + Clone + PartialEq + 'static> {
+    props: AutoFetchProps<REQ>,
+    state: FetchState<REQ::ResponseBody>,
+    link: ComponentLink<Self>,
+    /// Handle to the request in flight, which cancels it when replaced or dropped.
+    in_flight: Option<SpawnHandle>,
+}
+
+impl<REQ: FetchRequest + Clone + PartialEq + 'static> AutoFetch<REQ> {
+    /// Makes the request held by the props, cancelling any request still in flight.
+    fn begin_fetch(&mut self) {
+        let fetch = fetch_request(&self.props.request);
+        let handle = self.link.send_future_cancelable(async move { FetchAction::from(fetch.await) });
+        self.in_flight = Some(handle);
+        self.state = std::mem::take(&mut self.state).fetching();
+    }
+}
+
+impl<REQ: FetchRequest + Clone + PartialEq + 'static> Component for AutoFetch<REQ> {
+    type Message = FetchAction<REQ::ResponseBody>;
+    type Properties = AutoFetchProps<REQ>;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        AutoFetch {
+            props,
+            state: FetchState::default(),
+            link,
+            in_flight: None,
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        self.begin_fetch();
+        true
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let state = std::mem::take(&mut self.state);
+        self.state = match msg {
+            FetchAction::NotFetching | FetchAction::Canceled => state.not_fetching(),
+            FetchAction::Fetching => state.fetching(),
+            FetchAction::Fetched(res) => state.fetched(res),
+            FetchAction::Failed(err) => state.failed(err),
+        };
+        if !self.state.is_fetching() {
+            self.in_flight = None;
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let request_changed = props.request != self.props.request;
+        self.props = props;
+        if request_changed {
+            self.begin_fetch();
+        }
+        true
+    }
+
+    fn view(&self) -> Html {
+        match &self.state {
+            FetchState::Failed(res, err) => match (&self.props.failed, res) {
+                (Some(failed), _) => failed(err),
+                (None, Some(res)) => (self.props.fetched)(res),
+                (None, None) => html! {},
+            },
+            FetchState::Fetched(res)
+            | FetchState::NotFetching(Some(res))
+            | FetchState::Fetching(Some(res)) => (self.props.fetched)(res),
+            FetchState::Fetching(None) => self.props.fetching.clone().unwrap_or_else(|| html! {}),
+            FetchState::NotFetching(None) => html! {},
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crate::fetch::query::with_query;
+use crate::fetch::request::request_headers;
+use crate::fetch::{FetchError, FetchRequest, Format};
+#[cfg(any(target_arch = "wasm32", test))]
+use crate::fetch::FetchResponse;
+use futures::future::ready;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+thread_local! {
+    /// The backend that requests are sent through instead of the browser, if one has been installed.
+    static BACKEND: RefCell<Option<Rc<dyn FetchBackend>>> = RefCell::new(None);
+}
+
+/// A request, as it is handed to a `FetchBackend`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendRequest {
+    /// The URL of the resource, including any query parameters.
+    pub url: String,
+    /// The HTTP method.
+    pub method: &'static str,
+    /// The headers, including any stored headers the request has opted into echoing.
+    pub headers: Vec<(String, String)>,
+    /// The serialized body, if the method has one.
+    pub body: Option<String>,
+}
+
+/// The future returned by a `FetchBackend`, which resolves to the text of the response.
+pub type BackendFuture = Pin<Box<dyn Future<Output = Result<String, FetchError>>>>;
+
+/// Abstraction over sending a request and receiving the text of its response.
+///
+/// Requests are made using the browser's fetch API by default.
+/// Installing a backend with `set_fetch_backend` will send requests made with `fetch_request`,
+/// `fetch_request_full` and `fetch_head` (and the functions built on them, like `Fetch::fetch`) through it instead,
+/// which allows components that make requests to be tested without a network,
+/// even when not targeting wasm.
+///
+/// Because backends deal in text, headers are not captured into the `HeaderStore`,
+/// and formats that use `Transport::Binary` aren't supported.
+/// Successful responses are reported with a 200 status code and no headers.
+pub trait FetchBackend {
+    /// Sends the request, resolving to the text of the response.
+    fn fetch(&self, request: BackendRequest) -> BackendFuture;
+}
+
+/// Sends every request made on this thread through the backend, instead of the browser.
+///
+/// A reference to the backend can be kept to inspect it later, eg. to check the requests made to a `MockBackend`.
+pub fn set_fetch_backend(backend: Rc<dyn FetchBackend>) {
+    BACKEND.with(|installed| *installed.borrow_mut() = Some(backend))
+}
+
+/// Removes the installed backend, so requests are made using the browser again.
+pub fn reset_fetch_backend() {
+    BACKEND.with(|installed| *installed.borrow_mut() = None)
+}
+
+/// Gets the installed backend, if any.
+pub(crate) fn installed_backend() -> Option<Rc<dyn FetchBackend>> {
+    BACKEND.with(|installed| installed.borrow().clone())
+}
+
+/// Backend that responds with canned responses, and records the requests that were made.
+///
+/// Requests to URLs without a response fail with a 404 `FetchError::ResponseError`.
+///
+/// # Example
+/// ```
+///# use std::rc::Rc;
+///# use yewtil::fetch::{set_fetch_backend, MockBackend};
+/// let backend = Rc::new(MockBackend::new());
+/// backend.respond("http://some_host_website.com/apples", Ok("[1, 2, 3]".to_string()));
+/// set_fetch_backend(backend.clone());
+///
+/// // Requests made on this thread now receive the canned response,
+/// // and can be inspected using `backend.requests()`.
+/// ```
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    responses: RefCell<HashMap<String, Result<String, FetchError>>>,
+    requests: RefCell<Vec<BackendRequest>>,
+}
+
+impl MockBackend {
+    /// Creates a backend without any responses.
+    pub fn new() -> Self {
+        MockBackend::default()
+    }
+
+    /// Sets the response to requests made to the URL.
+    ///
+    /// The URL must match exactly, including any query parameters.
+    pub fn respond(&self, url: impl Into<String>, response: Result<String, FetchError>) {
+        self.responses.borrow_mut().insert(url.into(), response);
+    }
+
+    /// Gets the requests that have been made, in the order they were made.
+    pub fn requests(&self) -> Vec<BackendRequest> {
+        self.requests.borrow().clone()
+    }
+}
+
+impl FetchBackend for MockBackend {
+    fn fetch(&self, request: BackendRequest) -> BackendFuture {
+        let response = self
+            .responses
+            .borrow()
+            .get(&request.url)
+            .cloned()
+            .unwrap_or_else(|| {
+                Err(FetchError::ResponseError {
+                    status_code: 404,
+                    response_body: String::new(),
+                    headers: vec![],
+                })
+            });
+        self.requests.borrow_mut().push(request);
+        Box::pin(ready(response))
+    }
+}
+
+/// Describes the request in the form that is handed to a backend.
+fn backend_request<T: FetchRequest>(request: &T) -> Result<BackendRequest, FetchError> {
+    let method = request.method();
+    let body = match method.data() {
+        Some(data) => {
+            Some(<T::Format>::serialize(data).ok_or(FetchError::CouldNotSerializeRequestBody)?)
+        }
+        None => None,
+    };
+    Ok(BackendRequest {
+        url: with_query(&request.url(), &request.query()),
+        method: method.as_method(),
+        headers: request_headers(request),
+        body,
+    })
+}
+
+/// Makes the request using the backend, deserializing the response.
+pub(crate) fn fetch_through<T: FetchRequest>(
+    backend: Rc<dyn FetchBackend>,
+    request: &T,
+) -> impl Future<Output = Result<T::ResponseBody, FetchError>> {
+    let sent = backend_request(request).map(|request| backend.fetch(request));
+    async move {
+        let text = sent?.await?;
+        <T::Format>::deserialize(&text)
+            .map_err(|error| FetchError::DeserializeError { error, content: text })
+    }
+}
+
+/// Makes the request using the backend, deserializing the response along with its status code and headers.
+#[cfg(any(target_arch = "wasm32", test))]
+pub(crate) fn fetch_full_through<T: FetchRequest>(
+    backend: Rc<dyn FetchBackend>,
+    request: &T,
+) -> impl Future<Output = Result<FetchResponse<T::ResponseBody>, FetchError>> {
+    let fetch = fetch_through(backend, request);
+    async move { fetch.await.map(successful) }
+}
+
+/// Makes the request using the backend, ignoring the body of the response.
+#[cfg(any(target_arch = "wasm32", test))]
+pub(crate) fn fetch_head_through<T: FetchRequest>(
+    backend: Rc<dyn FetchBackend>,
+    request: &T,
+) -> impl Future<Output = Result<FetchResponse<()>, FetchError>> {
+    let sent = backend_request(request).map(|request| backend.fetch(request));
+    async move {
+        sent?.await?;
+        Ok(successful(()))
+    }
+}
+
+/// Describes a successful response from a backend, which doesn't provide a status code or headers.
+#[cfg(any(target_arch = "wasm32", test))]
+fn successful<T>(body: T) -> FetchResponse<T> {
+    FetchResponse {
+        body,
+        status: 200,
+        headers: vec![],
+    }
+}
+
+/// Makes a fetch request through the installed `FetchBackend`.
+///
+/// Requests can only be made using the browser when targeting wasm,
+/// so on other targets this fails with `FetchError::CouldNotCreateFetchFuture` unless a backend is installed.
+#[cfg(not(any(target_arch = "wasm32", test)))]
+pub fn fetch_request<T: FetchRequest>(
+    request: &T,
+) -> impl Future<Output = Result<T::ResponseBody, FetchError>> {
+    let fetch = installed_backend().map(|backend| fetch_through(backend, request));
+    async move {
+        match fetch {
+            Some(fetch) => fetch.await,
+            None => Err(FetchError::CouldNotCreateFetchFuture),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fetch::{Json, MethodBody};
+    use futures::executor::block_on;
+
+    struct AddAppleRequest(String);
+    impl FetchRequest for AddAppleRequest {
+        type RequestBody = String;
+        type ResponseBody = Vec<String>;
+        type Format = Json;
+
+        fn url(&self) -> String {
+            "http://some_host_website.com/apples".to_string()
+        }
+
+        fn method(&self) -> MethodBody<Self::RequestBody> {
+            MethodBody::Post(&self.0)
+        }
+
+        fn headers(&self) -> Vec<(String, String)> {
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        }
+
+        fn query(&self) -> Vec<(String, String)> {
+            vec![("variety".to_string(), "new".to_string())]
+        }
+    }
+
+    #[test]
+    fn mock_backend_receives_request_and_returns_canned_response() {
+        let backend = Rc::new(MockBackend::new());
+        backend.respond(
+            "http://some_host_website.com/apples?variety=new",
+            Ok(r#"["Gala", "Fuji"]"#.to_string()),
+        );
+
+        let response = block_on(fetch_through(backend.clone(), &AddAppleRequest("Fuji".to_string())));
+        assert_eq!(response, Ok(vec!["Gala".to_string(), "Fuji".to_string()]));
+        assert_eq!(
+            backend.requests(),
+            vec![BackendRequest {
+                url: "http://some_host_website.com/apples?variety=new".to_string(),
+                method: "POST",
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: Some(r#""Fuji""#.to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn mock_backend_responds_with_not_found_for_unknown_urls() {
+        let backend = Rc::new(MockBackend::new());
+        let response = block_on(fetch_through(backend, &AddAppleRequest("Fuji".to_string())));
+        assert!(matches!(
+            response,
+            Err(FetchError::ResponseError { status_code: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn deserialization_failure_reports_error_and_content() {
+        let backend = Rc::new(MockBackend::new());
+        backend.respond(
+            "http://some_host_website.com/apples?variety=new",
+            Ok("not json".to_string()),
+        );
+
+        match block_on(fetch_through(backend, &AddAppleRequest("Fuji".to_string()))) {
+            Err(FetchError::DeserializeError { error, content }) => {
+                assert!(error.starts_with("expected ident"), "unexpected error: {}", error);
+                assert_eq!(content, "not json");
+            }
+            other => panic!("Expected a deserialization error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use crate::fetch::{AbortHandle, FetchError, FetchRequest, FetchResponse, Format, HeaderStore, Transport};
+use crate::fetch::backend::{fetch_full_through, fetch_head_through, installed_backend};
+use crate::fetch::request::request_headers;
+use crate::fetch::response::header_pairs;
+use crate::fetch::error::js_error_message;
+use crate::fetch::timeout::with_timeout;
+use crate::fetch::query::with_query;
+use futures::future::Either;
+use wasm_bindgen::JsValue;
+use js_sys::Uint8Array;
+use web_sys::{AbortSignal, Request, RequestInit, RequestMode, Response, Window};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use std::future::Future;
+use std::marker::PhantomData;
+
+pub fn create_request<T: FetchRequest>(request: &T) -> Result<Request, FetchError> {
+    create_request_with_signal(request, None)
+}
+
+/// Creates the request, optionally attaching a signal that can be used to abort it.
+///
+/// If the signal is aborted while the request is in flight,
+/// fetching it will resolve to `FetchError::Aborted`.
+pub fn create_request_with_signal<T: FetchRequest>(request: &T, signal: Option<&AbortSignal>) -> Result<Request, FetchError> {
+    let method = request.method();
+    let headers = request_headers(request);
+    let headers = JsValue::from_serde(&headers).expect("Convert Headers to Tuple");
+
+    // configure options for the request
+    let mut opts = RequestInit::new();
+    opts.method(method.as_method());
+    opts.body(method.as_body::<T::Format>()?.as_ref());
+    opts.headers(&headers);
+    opts.signal(signal);
+    opts.credentials(request.credentials());
+    opts.redirect(request.redirect());
+    opts.cache(request.cache());
+
+    // TODO, see if there are more options that can be specified.
+    if request.use_cors() {
+        opts.mode(RequestMode::Cors);
+    }
+
+    // Create the request
+    Request::new_with_str_and_init(
+        &with_query(&request.url(), &request.query()),
+        &opts,
+    )
+        .map_err(|e| FetchError::CouldNotCreateRequest(js_error_message(&e)))
+
+}
+
+/// Fetch a resource, returning a result of the expected response,
+/// or an error indicating what went wrong.
+///
+/// To make the request abortable, create it with `create_request_with_signal`.
+///
+/// Because this only has access to the created `Request`, the `FetchRequest`'s `timeout` isn't applied,
+/// and the request is always made using the browser, even if a `FetchBackend` has been installed,
+/// as the `Request`'s body can't be read without sending it.
+/// Use `fetch_request` instead, which doesn't have these limitations.
+#[deprecated(note = "Use `fetch_request` instead, which applies the timeout and uses the installed `FetchBackend`")]
+#[allow(deprecated)]
+pub async fn fetch_resource<T: FetchRequest>(request: Result<Request, FetchError>, req_type: PhantomData<T>) -> Result<T::ResponseBody, FetchError> {
+    fetch_resource_full(request, req_type).await
+        .map(|response| response.body)
+}
+
+/// Fetch a resource, returning a result of the expected response along with its status code and headers,
+/// or an error indicating what went wrong.
+///
+/// Like `fetch_resource`, this ignores the `timeout` and any installed `FetchBackend`.
+/// Use `fetch_request_full` instead.
+#[deprecated(note = "Use `fetch_request_full` instead, which applies the timeout and uses the installed `FetchBackend`")]
+pub async fn fetch_resource_full<T: FetchRequest>(request: Result<Request, FetchError>, _req_type: PhantomData<T>) -> Result<FetchResponse<T::ResponseBody>, FetchError> {
+    send_and_read::<T>(request?, &[]).await
+}
+
+/// Fetch a resource, returning the future along with an `AbortHandle` that can cancel it.
+///
+/// If the handle is aborted or dropped before the request completes,
+/// the future will resolve to `FetchError::Aborted`.
+/// This makes it easy to supersede an older request: store the handle,
+/// and replacing it with the handle of a new request will cancel the old one.
+pub fn fetch_resource_abortable<T: FetchRequest>(request: &T) -> (impl Future<Output=Result<T::ResponseBody, FetchError>>, AbortHandle) {
+    let handle = AbortHandle::new();
+    let fetch = fetch_request_with_signal(request, Some(&handle.signal()));
+    (fetch, handle)
+}
+
+/// Makes a fetch request, returning a future that resolves to the expected response,
+/// or an error indicating what went wrong.
+///
+/// Because this has access to the `FetchRequest` itself,
+/// it will also capture any headers listed in `capture_headers` into the `HeaderStore`,
+/// and will fail with `FetchError::Timeout` if the request takes longer than its `timeout`.
+///
+/// If a `FetchBackend` has been installed, the request is sent through it instead of the browser.
+///
+/// The returned future doesn't borrow the request, so it can be handed to `LinkFuture::send_future`.
+pub fn fetch_request<T: FetchRequest>(request: &T) -> impl Future<Output=Result<T::ResponseBody, FetchError>> {
+    fetch_request_with_signal(request, None)
+}
+
+/// Makes a fetch request, returning a future that resolves to the expected response
+/// along with its status code and headers, or an error indicating what went wrong.
+///
+/// Like `fetch_request`, this captures the headers listed in `capture_headers`, respects the request's `timeout`,
+/// and sends the request through the installed `FetchBackend`, if there is one.
+pub fn fetch_request_full<T: FetchRequest>(request: &T) -> impl Future<Output=Result<FetchResponse<T::ResponseBody>, FetchError>> {
+    fetch_request_full_with_signal(request, None)
+}
+
+/// Makes a fetch request for only the status code and headers of the response, without reading its body.
+///
+/// This is meant for `MethodBody::Head` requests, which are used to check whether a resource exists,
+/// or to read metadata like `Last-Modified` or `Content-Length` without downloading the resource.
+/// Like `fetch_request`, it captures the headers listed in `capture_headers`, respects the request's `timeout`,
+/// and sends the request through the installed `FetchBackend`, if there is one.
+pub fn fetch_head<T: FetchRequest>(request: &T) -> impl Future<Output=Result<FetchResponse<()>, FetchError>> {
+    let timeout = request.timeout();
+    let fetch = match installed_backend() {
+        Some(backend) => Either::Left(fetch_head_through(backend, request)),
+        None => Either::Right(fetch_head_from_browser(request)),
+    };
+    with_timeout(fetch, timeout)
+}
+
+/// Makes a fetch request for only the status code and headers of the response, using the browser's fetch API.
+fn fetch_head_from_browser<T: FetchRequest>(request: &T) -> impl Future<Output=Result<FetchResponse<()>, FetchError>> {
+    let captured_headers: Vec<String> = request.capture_headers()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let request = create_request(request);
+    async move {
+        let resp = send_request(&request?).await?;
+        HeaderStore::capture(&resp.headers(), &captured_headers);
+        read_head(&resp)
+    }
+}
+
+/// Makes a fetch request that can be aborted using the provided signal.
+pub(crate) fn fetch_request_with_signal<T: FetchRequest>(request: &T, signal: Option<&AbortSignal>) -> impl Future<Output=Result<T::ResponseBody, FetchError>> {
+    let fetch = fetch_request_full_with_signal(request, signal);
+    async move {
+        let response = fetch.await?;
+        Ok(response.body)
+    }
+}
+
+/// Makes a fetch request that can be aborted using the provided signal,
+/// resolving to the response along with its status code and headers.
+fn fetch_request_full_with_signal<T: FetchRequest>(request: &T, signal: Option<&AbortSignal>) -> impl Future<Output=Result<FetchResponse<T::ResponseBody>, FetchError>> {
+    let timeout = request.timeout();
+    let fetch = match installed_backend() {
+        Some(backend) => Either::Left(fetch_full_through(backend, request)),
+        None => Either::Right(fetch_from_browser(request, signal)),
+    };
+    with_timeout(fetch, timeout)
+}
+
+/// Makes a fetch request using the browser's fetch API.
+fn fetch_from_browser<T: FetchRequest>(request: &T, signal: Option<&AbortSignal>) -> impl Future<Output=Result<FetchResponse<T::ResponseBody>, FetchError>> {
+    let captured_headers: Vec<String> = request.capture_headers()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let request = create_request_with_signal(request, signal);
+    async move {
+        send_and_read::<T>(request?, &captured_headers).await
+    }
+}
+
+/// Sends the request and reads its response, capturing the named headers.
+///
+/// Any failure that happens after the request's signal has been aborted is reported as `FetchError::Aborted`.
+async fn send_and_read<T: FetchRequest>(request: Request, captured_headers: &[String]) -> Result<FetchResponse<T::ResponseBody>, FetchError> {
+    let result = async {
+        let resp = send_request(&request).await?;
+        HeaderStore::capture(&resp.headers(), captured_headers);
+        read_response::<T>(resp).await
+    }.await;
+    result.map_err(|err| aborted_or(request.signal().aborted(), err))
+}
+
+/// Replaces the error with `FetchError::Aborted` if the request was aborted.
+fn aborted_or(aborted: bool, err: FetchError) -> FetchError {
+    if aborted {
+        FetchError::Aborted
+    } else {
+        err
+    }
+}
+
+/// Send the request, resolving it to a response.
+pub(crate) async fn send_request(request: &Request) -> Result<Response, FetchError> {
+    let window: Window = web_sys::window().unwrap();
+    let resp_value = JsFuture::from(window.fetch_with_request(request))
+        .await
+        .map_err(|_| FetchError::CouldNotCreateFetchFuture)?;
+    debug_assert!(resp_value.is_instance_of::<Response>());
+    let resp: Response = resp_value.dyn_into().unwrap();
+    Ok(resp)
+}
+
+/// Reads the body of the response as bytes.
+///
+/// Text is decoded by the browser before being encoded as UTF-8,
+/// so text-based formats don't need to be concerned with the response's charset.
+async fn read_body(resp: &Response, transport: Transport) -> Result<Vec<u8>, FetchError> {
+    match transport {
+        Transport::Text => {
+            let text = JsFuture::from(resp.text().map_err(|_| FetchError::TextNotAvailable)?)
+                .await
+                .map_err(|_| FetchError::TextNotAvailable)?;
+            Ok(text.as_string().unwrap().into_bytes())
+        }
+        Transport::Binary => {
+            let buffer = JsFuture::from(resp.array_buffer().map_err(|_| FetchError::TextNotAvailable)?)
+                .await
+                .map_err(|_| FetchError::TextNotAvailable)?;
+            let array = Uint8Array::new(&buffer);
+            let mut bytes = vec![0; array.length() as usize];
+            array.copy_to(&mut bytes);
+            Ok(bytes)
+        }
+    }
+}
+
+/// Process the response, reading only its status code and headers.
+fn read_head(resp: &Response) -> Result<FetchResponse<()>, FetchError> {
+    let headers = header_pairs(&resp.headers());
+    if !resp.ok() {
+        return Err(FetchError::ResponseError {status_code: resp.status(), response_body: String::new(), headers})
+    }
+
+    Ok(FetchResponse {
+        body: (),
+        status: resp.status(),
+        headers,
+    })
+}
+
+/// Process the response, deserializing its body.
+async fn read_response<T: FetchRequest>(resp: Response) -> Result<FetchResponse<T::ResponseBody>, FetchError> {
+    let body = read_body(&resp, <T::Format>::TRANSPORT).await?;
+    parse_response::<T>(&resp, body)
+}
+
+/// Deserializes the body that was read from the response.
+pub(crate) fn parse_response<T: FetchRequest>(resp: &Response, body: Vec<u8>) -> Result<FetchResponse<T::ResponseBody>, FetchError> {
+    // If the response isn't ok, then return an error without trying to deserialize.
+    if !resp.ok() {
+        let response_body = String::from_utf8_lossy(&body).into_owned();
+        let headers = header_pairs(&resp.headers());
+        return Err(FetchError::ResponseError {status_code: resp.status(), response_body, headers})
+    }
+
+
+    let deserialized = <T::Format>::deserialize_bytes(&body)
+        .map_err(|error| {
+            let content = String::from_utf8_lossy(&body).into_owned();
+            FetchError::DeserializeError{error, content}
+        })?;
+
+    Ok(FetchResponse {
+        body: deserialized,
+        status: resp.status(),
+        headers: header_pairs(&resp.headers()),
+    })
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fetch::{reset_fetch_backend, set_fetch_backend, Json, MethodBody, MockBackend};
+    use futures::executor::block_on;
+    use std::rc::Rc;
+
+    struct ApplesRequest {
+        head: bool,
+    }
+    impl FetchRequest for ApplesRequest {
+        type RequestBody = ();
+        type ResponseBody = Vec<String>;
+        type Format = Json;
+
+        fn url(&self) -> String {
+            "http://some_host_website.com/apples".to_string()
+        }
+
+        fn method(&self) -> MethodBody<Self::RequestBody> {
+            if self.head {
+                MethodBody::Head
+            } else {
+                MethodBody::Get
+            }
+        }
+
+        fn headers(&self) -> Vec<(String, String)> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn failure_after_abort_is_reported_as_aborted() {
+        assert_eq!(aborted_or(true, FetchError::CouldNotCreateFetchFuture), FetchError::Aborted);
+        assert_eq!(
+            aborted_or(false, FetchError::CouldNotCreateFetchFuture),
+            FetchError::CouldNotCreateFetchFuture
+        );
+    }
+
+    #[test]
+    fn full_and_head_requests_are_sent_through_the_installed_backend() {
+        let backend = Rc::new(MockBackend::new());
+        backend.respond("http://some_host_website.com/apples", Ok(r#"["Gala"]"#.to_string()));
+        set_fetch_backend(backend.clone());
+
+        let response = block_on(fetch_request_full(&ApplesRequest { head: false }));
+        assert_eq!(
+            response,
+            Ok(FetchResponse {
+                body: vec!["Gala".to_string()],
+                status: 200,
+                headers: vec![],
+            })
+        );
+
+        let response = block_on(fetch_head(&ApplesRequest { head: true }));
+        assert_eq!(response.map(|response| response.status), Ok(200));
+        assert_eq!(backend.requests()[1].method, "HEAD");
+
+        reset_fetch_backend();
+    }
+}
@@ -0,0 +1,169 @@
+use crate::fetch::Format;
+use csv::StringRecord;
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer, Unexpected, Visitor};
+use serde::{forward_to_deserialize_any, Deserializer, Serialize};
+
+/// Transport data using the CSV format.
+///
+/// The response body is deserialized as a sequence of rows, so it should be a collection
+/// (eg. `Vec<Row>`) of structs whose field names match the headers in the first line of the CSV.
+///
+/// Only responses are supported. Serializing a request body with this format will fail.
+///
+/// # Example
+/// ```
+///# use yewtil::fetch::{Csv, Format};
+///# use serde::Deserialize;
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Fruit {
+///     name: String,
+///     count: u32,
+/// }
+///
+/// let fruits: Vec<Fruit> = Csv::deserialize("name,count\napple,3\npear,5").unwrap();
+/// assert_eq!(fruits[1], Fruit { name: "pear".to_string(), count: 5 });
+/// ```
+pub struct Csv;
+impl Format for Csv {
+    fn serialize<T: Serialize>(_t: &T) -> Option<String> {
+        None
+    }
+
+    fn deserialize<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+        let mut reader = csv::Reader::from_reader(s.as_bytes());
+        let headers = reader.headers().map_err(|err| err.to_string())?.clone();
+        let rows = reader
+            .records()
+            .collect::<Result<Vec<StringRecord>, _>>()
+            .map_err(|err| err.to_string())?;
+        let rows = rows.iter().map(|fields| Row {
+            headers: &headers,
+            fields,
+        });
+        T::deserialize(SeqDeserializer::new(rows)).map_err(|err| err.to_string())
+    }
+}
+
+/// A row of the CSV, which deserializes as a map of headers to fields.
+struct Row<'a> {
+    headers: &'a StringRecord,
+    fields: &'a StringRecord,
+}
+
+impl<'de, 'a> Deserializer<'de> for Row<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let entries = self.headers.iter().zip(self.fields.iter().map(Field));
+        let mut map = MapDeserializer::new(entries);
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, Error> for Row<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// A single field of the CSV, which is parsed into whatever type is expected of it.
+struct Field<'a>(&'a str);
+
+/// Implements deserialization methods by parsing the field into the requested type.
+macro_rules! parse_field {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for Field<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.0)
+    }
+
+    /// Empty fields are treated as missing values.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    parse_field! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct newtype_struct seq
+        tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, Error> for Field<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Fruit {
+        name: String,
+        count: Option<u32>,
+    }
+
+    #[test]
+    fn empty_fields_are_none() {
+        let fruits: Vec<Fruit> = Csv::deserialize("name,count\napple,\npear,5").unwrap();
+        assert_eq!(
+            fruits,
+            vec![
+                Fruit { name: "apple".to_string(), count: None },
+                Fruit { name: "pear".to_string(), count: Some(5) },
+            ]
+        );
+    }
+
+    #[test]
+    fn unparsable_fields_fail() {
+        assert!(Csv::deserialize::<Vec<Fruit>>("name,count\napple,many").is_err());
+    }
+}
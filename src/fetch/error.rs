@@ -1,21 +1,30 @@
-use wasm_bindgen::JsValue;
+#[cfg(any(target_arch = "wasm32", test))]
+use wasm_bindgen::{JsCast, JsValue};
 
 /// A representation of an error that may occur when making a fetch request.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum FetchError {
     /// The response could not be deserialized.
     DeserializeError{error: String, content: String},
     /// The response had an error code.
-    ResponseError{status_code: u16, response_body: String},
-    /// Text was not available on the response.
+    ///
+    /// The response's headers are included, so details like `Retry-After` can be inspected.
+    ResponseError{status_code: u16, response_body: String, headers: Vec<(String, String)>},
+    /// The body was not available on the response.
     // TODO, this might get thrown in unexpected circumstances.
     TextNotAvailable,
     /// The Fetch Future could not be created due to a misconfiguration.
     CouldNotCreateFetchFuture,
     /// The request could cont be created due to a misconfiguration.
-    CouldNotCreateRequest(JsValue), // TODO, convert this to a string or more structured error - implement Hash on this and related structs.
+    ///
+    /// Contains the message of the error thrown by the browser.
+    CouldNotCreateRequest(String),
     /// Could not serialize the request body.
-    CouldNotSerializeRequestBody
+    CouldNotSerializeRequestBody,
+    /// The request was aborted using its `AbortSignal`.
+    Aborted,
+    /// The request didn't complete within its timeout.
+    Timeout,
 }
 
 impl std::fmt::Display for FetchError {
@@ -24,24 +33,42 @@ impl std::fmt::Display for FetchError {
             FetchError::DeserializeError {error, content} => {
                 f.write_str(&format!("Could not deserialize a successful request. With error: {}, and content: {}", error, content))
             }
-            FetchError::ResponseError { status_code, response_body} => {
+            FetchError::ResponseError { status_code, response_body, ..} => {
                 f.write_str(&format!("The server returned a response with code: {}, and body: {}", status_code, response_body))
             }
             FetchError::TextNotAvailable => {
-                f.write_str("The text could not be extracted from the response.")
+                f.write_str("The body could not be extracted from the response.")
             }
             FetchError::CouldNotCreateFetchFuture => {
                 f.write_str("Could not create a fetch future.")
             }
-            FetchError::CouldNotCreateRequest(_) => {
-                f.write_str("Could not create a fetch request.")
+            FetchError::CouldNotCreateRequest(message) => {
+                f.write_str(&format!("Could not create a fetch request: {}", message))
             }
             FetchError::CouldNotSerializeRequestBody => {
                 f.write_str("Could not serialize the body in the fetch request.")
             }
+            FetchError::Aborted => {
+                f.write_str("The fetch request was aborted.")
+            }
+            FetchError::Timeout => {
+                f.write_str("The fetch request timed out.")
+            }
         }
     }
 }
 
 impl std::error::Error for FetchError {
-}
\ No newline at end of file
+}
+
+/// Gets a message describing an error thrown from JavaScript.
+#[cfg(any(target_arch = "wasm32", test))]
+pub(crate) fn js_error_message(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        String::from(error.message())
+    } else if let Some(message) = value.as_string() {
+        message
+    } else {
+        format!("{:?}", value)
+    }
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(any(target_arch = "wasm32", test))]
+use web_sys::Headers;
+
+thread_local! {
+    /// Header values captured from responses, keyed by their lowercase names.
+    static STORED_HEADERS: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Storage for headers that are captured from responses and sent along with later requests.
+///
+/// Browsers don't expose `Set-Cookie` to scripts, so APIs will sometimes hand out session tokens
+/// in a custom header (eg. `X-Auth-Token`) instead, expecting it to be echoed back on subsequent requests.
+/// A `FetchRequest` can opt into this by listing the header names in `capture_headers`.
+///
+/// Header names are treated case-insensitively.
+/// Because it is backed by a thread-local, the store is shared by every request made on the same thread.
+pub struct HeaderStore;
+
+impl HeaderStore {
+    /// Gets the stored value of a header.
+    pub fn get(name: &str) -> Option<String> {
+        STORED_HEADERS.with(|headers| headers.borrow().get(&name.to_lowercase()).cloned())
+    }
+
+    /// Stores the value of a header, replacing any value that was previously captured for it.
+    pub fn set(name: &str, value: String) {
+        STORED_HEADERS.with(|headers| {
+            headers.borrow_mut().insert(name.to_lowercase(), value);
+        })
+    }
+
+    /// Removes a header from the store, returning its value if it was present.
+    pub fn remove(name: &str) -> Option<String> {
+        STORED_HEADERS.with(|headers| headers.borrow_mut().remove(&name.to_lowercase()))
+    }
+
+    /// Removes all stored headers.
+    ///
+    /// This is useful for discarding session tokens when logging out.
+    pub fn clear() {
+        STORED_HEADERS.with(|headers| headers.borrow_mut().clear())
+    }
+
+    /// Stores the values of the named headers that are present on a response.
+    #[cfg(any(target_arch = "wasm32", test))]
+    pub(crate) fn capture(response_headers: &Headers, names: &[String]) {
+        for name in names {
+            if let Ok(Some(value)) = response_headers.get(name) {
+                Self::set(name, value);
+            }
+        }
+    }
+
+    /// Appends stored values of the named headers to the request's headers.
+    ///
+    /// Headers that were already explicitly provided by the request are left alone.
+    pub(crate) fn inject(headers: &mut Vec<(String, String)>, names: &[&str]) {
+        for name in names {
+            let already_present = headers
+                .iter()
+                .any(|(existing, _)| existing.eq_ignore_ascii_case(name));
+            if already_present {
+                continue;
+            }
+            if let Some(value) = Self::get(name) {
+                headers.push((name.to_string(), value));
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::fetch::{Format, Transport};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Transport data using the MessagePack format.
+///
+/// Structs are serialized as maps, so that their fields can be identified by name.
+/// MessagePack is a binary format, so the text-based methods always fail.
+///
+/// # Example
+/// ```
+///# use yewtil::fetch::{Format, MsgPack};
+/// let bytes = MsgPack::serialize_bytes(&vec![1, 2, 3]).unwrap();
+/// assert_eq!(MsgPack::deserialize_bytes::<Vec<u8>>(&bytes), Ok(vec![1, 2, 3]));
+/// ```
+pub struct MsgPack;
+impl Format for MsgPack {
+    const TRANSPORT: Transport = Transport::Binary;
+
+    fn serialize<T: Serialize>(_t: &T) -> Option<String> {
+        None
+    }
+
+    fn deserialize<T: DeserializeOwned>(_s: &str) -> Result<T, String> {
+        Err("MessagePack can't be deserialized from text".to_string())
+    }
+
+    fn serialize_bytes<T: Serialize>(t: &T) -> Option<Vec<u8>> {
+        rmp_serde::to_vec_named(t).ok()
+    }
+
+    fn deserialize_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+        rmp_serde::from_slice(bytes).map_err(|err| err.to_string())
+    }
+
+    fn content_type() -> &'static str {
+        "application/msgpack"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Apple {
+        variety: String,
+        weight: u32,
+    }
+
+    #[test]
+    fn struct_round_trips() {
+        let apple = Apple {
+            variety: "Gala".to_string(),
+            weight: 150,
+        };
+        let bytes = MsgPack::serialize_bytes(&apple).unwrap();
+        assert_eq!(MsgPack::deserialize_bytes::<Apple>(&bytes), Ok(apple));
+    }
+}
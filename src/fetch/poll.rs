@@ -0,0 +1,142 @@
+use crate::fetch::{fetch_request, FetchAction, FetchError, FetchRequest};
+use crate::future::IntervalHandle;
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use futures::stream::{self, Stream, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+
+/// Repeatedly makes a fetch request, yielding a `FetchAction` every time a request completes.
+///
+/// This is intended for comet/long-poll endpoints, where the server holds the request open until
+/// it has something to report.
+/// A new request is created by calling `request_factory` and is sent as soon as the previous one
+/// completes, regardless of whether the previous request succeeded.
+/// Polling stops once the stream is dropped.
+///
+/// The stream can be handed to `LinkFuture::send_stream` along with a mapping to a message type
+/// to feed every result into a component's update loop.
+///
+/// # Note
+/// Like the rest of the fetch module, the requests are made using `web_sys`, so the stream
+/// must be polled from within a wasm environment that has a `window`,
+/// typically by spawning it with `wasm_bindgen_futures` or via `LinkFuture`.
+pub fn fetch_long_poll<T: FetchRequest>(
+    request_factory: impl Fn() -> T,
+) -> impl Stream<Item = FetchAction<T::ResponseBody>> {
+    repeat_fetch(request_factory, |request: T| fetch_request(&request))
+}
+
+/// Repeatedly makes a fetch request at a fixed interval, yielding a message every time a request completes.
+///
+/// The first request is made as soon as the stream is polled, and later ones every `interval` after that.
+/// If a request is still in flight when the interval elapses, that tick is skipped rather than
+/// starting another request, so slow responses won't cause requests to pile up.
+///
+/// Polling continues until the returned `PollHandle` is dropped, so it should be stored in the
+/// component that receives the messages, which can hand the stream to `LinkFuture::send_stream`.
+/// The stream ends once the handle has been dropped.
+pub fn fetch_poll<T: FetchRequest, Msg>(
+    request: T,
+    interval: Duration,
+    to_msg: impl Fn(FetchAction<T::ResponseBody>) -> Msg,
+) -> (impl Stream<Item = Msg>, PollHandle) {
+    let (sender, ticks) = mpsc::unbounded();
+    let _ = sender.unbounded_send(());
+    let handle = IntervalHandle::new(interval, move || {
+        let _ = sender.unbounded_send(());
+    });
+
+    let stream = fetch_on_tick(ticks, move || fetch_request(&request)).map(to_msg);
+    (stream, handle)
+}
+
+/// A handle to a `fetch_poll`ing stream, which stops polling when it is dropped.
+pub type PollHandle = IntervalHandle;
+
+/// Makes a request for every tick, skipping ticks that arrive while a request is in flight.
+fn fetch_on_tick<RES, FUT>(
+    ticks: UnboundedReceiver<()>,
+    fetch: impl Fn() -> FUT,
+) -> impl Stream<Item = FetchAction<RES>>
+where
+    FUT: Future<Output = Result<RES, FetchError>>,
+{
+    stream::unfold((ticks, fetch), |(mut ticks, fetch)| async move {
+        ticks.next().await?;
+        let action = FetchAction::from(fetch().await);
+        // Ticks that arrived while the request was in flight are skipped.
+        while let Ok(Some(())) = ticks.try_next() {}
+        Some((action, (ticks, fetch)))
+    })
+}
+
+/// Re-issues a request created by the factory each time the previous one resolves.
+fn repeat_fetch<T, RES, FUT>(
+    request_factory: impl Fn() -> T,
+    fetch: impl Fn(T) -> FUT,
+) -> impl Stream<Item = FetchAction<RES>>
+where
+    FUT: Future<Output = Result<RES, FetchError>>,
+{
+    stream::unfold(
+        (request_factory, fetch),
+        |(request_factory, fetch)| async move {
+            let action = FetchAction::from(fetch(request_factory()).await);
+            Some((action, (request_factory, fetch)))
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::future::ready;
+    use std::cell::Cell;
+
+    #[test]
+    fn long_poll_reissues_request_after_each_completion() {
+        let requests_made = Cell::new(0);
+        let factory = || {
+            requests_made.set(requests_made.get() + 1);
+            requests_made.get()
+        };
+        let stream = repeat_fetch(factory, |request: usize| {
+            if request == 2 {
+                ready(Err(FetchError::TextNotAvailable))
+            } else {
+                ready(Ok(request * 10))
+            }
+        });
+
+        let actions: Vec<FetchAction<usize>> = block_on(stream.take(3).collect());
+        assert_eq!(
+            actions,
+            vec![
+                FetchAction::Fetched(10),
+                FetchAction::Failed(FetchError::TextNotAvailable),
+                FetchAction::Fetched(30),
+            ]
+        );
+        assert_eq!(requests_made.get(), 3);
+    }
+
+    #[test]
+    fn poll_skips_ticks_while_request_is_in_flight() {
+        let (sender, ticks) = mpsc::unbounded();
+        for _ in 0..3 {
+            sender.unbounded_send(()).unwrap();
+        }
+        drop(sender);
+
+        let requests_made = Cell::new(0);
+        let stream = fetch_on_tick(ticks, || {
+            requests_made.set(requests_made.get() + 1);
+            ready(Ok(requests_made.get()))
+        });
+
+        let actions: Vec<FetchAction<usize>> = block_on(stream.collect());
+        assert_eq!(actions, vec![FetchAction::Fetched(1)]);
+        assert_eq!(requests_made.get(), 1);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::fetch::client::{parse_response, send_request};
+use crate::fetch::response::parse_content_length;
+use crate::fetch::timeout::with_timeout;
+use crate::fetch::{create_request, FetchAction, FetchError, FetchRequest, HeaderStore};
+use futures::channel::mpsc;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use js_sys::{Function, Promise, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+/// Makes a fetch request, yielding a message as each chunk of the response's body is received,
+/// followed by a message containing the `FetchAction` once the request completes.
+///
+/// `on_progress` is called with the number of bytes received so far, and the response's `Content-Length`
+/// if it was provided, which makes it possible to show a progress bar for large downloads.
+/// Because the `Content-Length` describes the body as it was sent,
+/// compressed responses may receive more bytes than it reports.
+///
+/// The body is read as it was sent, without being decoded by the browser,
+/// so formats that use `Transport::Text` will receive it as UTF-8.
+///
+/// The stream can be handed to `LinkFuture::send_stream` to feed every message into a component's update loop.
+/// The request is always made using the browser, even if a `FetchBackend` has been installed,
+/// because backends don't report the progress of a response.
+pub fn fetch_resource_with_progress<T: FetchRequest, Msg>(
+    request: &T,
+    on_progress: impl Fn(u64, Option<u64>) -> Msg,
+    to_msg: impl Fn(FetchAction<T::ResponseBody>) -> Msg,
+) -> impl Stream<Item = Msg> {
+    let timeout = request.timeout();
+    let captured_headers: Vec<String> = request
+        .capture_headers()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let request = create_request(request);
+    let (sender, messages) = mpsc::unbounded();
+    let fetch = async move {
+        let fetch = async {
+            let resp = send_request(&request?).await?;
+            HeaderStore::capture(&resp.headers(), &captured_headers);
+            let body = read_body_with_progress(&resp, |received, total| {
+                let _ = sender.unbounded_send(on_progress(received, total));
+            })
+            .await?;
+            parse_response::<T>(&resp, body)
+        };
+        let result = with_timeout(fetch, timeout).await;
+        let _ = sender.unbounded_send(to_msg(result.map(|response| response.body).into()));
+    };
+    // Every message is sent through the channel, so the fetch is only polled to drive it.
+    // The channel ends once the fetch completes and drops its sender.
+    stream::select(messages, stream::once(fetch).filter_map(|()| future::ready(None)))
+}
+
+/// Reads the body of the response one chunk at a time,
+/// calling `on_chunk` with the number of bytes received so far and the expected total.
+async fn read_body_with_progress(resp: &Response, on_chunk: impl Fn(u64, Option<u64>)) -> Result<Vec<u8>, FetchError> {
+    let total = resp
+        .headers()
+        .get("Content-Length")
+        .ok()
+        .flatten()
+        .and_then(|length| parse_content_length(&length));
+    let reader: JsValue = match resp.body() {
+        Some(stream) => stream.get_reader().into(),
+        None => return Ok(Vec::new()),
+    };
+    let read: Function = Reflect::get(&reader, &JsValue::from_str("read"))
+        .and_then(|read| read.dyn_into())
+        .map_err(|_| FetchError::TextNotAvailable)?;
+
+    // Reserving the expected size up front avoids repeatedly reallocating large bodies as chunks arrive.
+    let mut body = Vec::with_capacity(initial_capacity(total));
+    loop {
+        let promise: Promise = read
+            .call0(&reader)
+            .and_then(|promise| promise.dyn_into())
+            .map_err(|_| FetchError::TextNotAvailable)?;
+        let chunk = JsFuture::from(promise)
+            .await
+            .map_err(|_| FetchError::TextNotAvailable)?;
+        let done = Reflect::get(&chunk, &JsValue::from_str("done"))
+            .ok()
+            .and_then(|done| done.as_bool())
+            .unwrap_or(true);
+        if done {
+            return Ok(body);
+        }
+        let value = Reflect::get(&chunk, &JsValue::from_str("value"))
+            .map_err(|_| FetchError::TextNotAvailable)?;
+        let array = Uint8Array::new(&value);
+        let start = body.len();
+        body.resize(start + array.length() as usize, 0);
+        array.copy_to(&mut body[start..]);
+        on_chunk(body.len() as u64, total);
+    }
+}
+
+/// Gets the capacity to reserve for a body of the expected size.
+///
+/// The size comes from the server, so it is limited to keep a bogus `Content-Length` from
+/// reserving more memory than the body could reasonably need.
+fn initial_capacity(total: Option<u64>) -> usize {
+    const MAX_CAPACITY: u64 = 64 * 1024 * 1024;
+    total.unwrap_or(0).min(MAX_CAPACITY) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn capacity_is_reserved_for_content_length() {
+        assert_eq!(initial_capacity(Some(1024)), 1024);
+        assert_eq!(initial_capacity(None), 0);
+    }
+
+    #[test]
+    fn capacity_is_limited() {
+        assert_eq!(initial_capacity(Some(u64::max_value())), 64 * 1024 * 1024);
+    }
+}
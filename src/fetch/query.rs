@@ -0,0 +1,77 @@
+use std::fmt::Write;
+
+/// Appends the URL-encoded query parameters to the URL.
+///
+/// Parameters are added after any query the URL already has, and before its fragment.
+pub(crate) fn with_query(url: &str, query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return url.to_string();
+    }
+    let (base, fragment) = match url.find('#') {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    };
+    let separator = if !base.contains('?') {
+        "?"
+    } else if base.ends_with('?') || base.ends_with('&') {
+        ""
+    } else {
+        "&"
+    };
+    let encoded = query
+        .iter()
+        .map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}{}{}{}", base, separator, encoded, fragment)
+}
+
+/// Percent-encodes every byte of the UTF-8 string, except for unreserved characters.
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => write!(encoded, "%{:02X}", byte).unwrap(),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn encodes_reserved_characters() {
+        assert_eq!(percent_encode("a b&c=d/é"), "a%20b%26c%3Dd%2F%C3%A9");
+        assert_eq!(percent_encode("safe-_.~"), "safe-_.~");
+    }
+
+    #[test]
+    fn appends_query_to_url() {
+        let query = params(&[("q", "red apples"), ("page", "2")]);
+        assert_eq!(
+            with_query("http://example.com/search", &query),
+            "http://example.com/search?q=red%20apples&page=2"
+        );
+        assert_eq!(
+            with_query("http://example.com/search?sort=asc#results", &query),
+            "http://example.com/search?sort=asc&q=red%20apples&page=2#results"
+        );
+    }
+
+    #[test]
+    fn empty_query_leaves_url_unchanged() {
+        assert_eq!(with_query("http://example.com/?", &[]), "http://example.com/?");
+    }
+}
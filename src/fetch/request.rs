@@ -1,24 +1,31 @@
-use crate::fetch::{FetchError};
+use crate::fetch::{FetchError, HeaderStore};
 use wasm_bindgen::JsValue;
+use js_sys::Uint8Array;
 use serde::{Serialize};
 use serde::de::DeserializeOwned;
-use web_sys::{Request, RequestInit, RequestMode, Response, Window};
-use wasm_bindgen::JsCast;
-use wasm_bindgen_futures::JsFuture;
-use std::marker::PhantomData;
+use serde::de::IntoDeserializer;
+use serde::de::value::StringDeserializer;
+use web_sys::{RequestCache, RequestCredentials, RequestRedirect};
+use std::time::Duration;
 
 /// An enum representing what method to use for the request,
 /// as well as a body if the method is able to have a body.
 ///
-/// Connect, Options, Trace are omitted because they are unlikely to be used in this scenario.
-/// Please open an issue if their absence is a problem for you.
+/// Connect is omitted because browsers forbid it from being used with fetch.
+/// Browsers forbid Trace as well, but it is included for environments that allow it.
 pub enum MethodBody<'a, T> {
     Head,
     Get,
     Delete,
+    Options,
+    Trace,
     Post(&'a T),
     Put(&'a T),
     Patch(&'a T),
+    /// A method that isn't otherwise listed, like non-standard methods used by some services.
+    ///
+    /// Requests using it are sent without a body.
+    Custom(&'static str),
 }
 
 impl <'a, T> MethodBody<'a, T> {
@@ -30,49 +37,138 @@ impl <'a, T> MethodBody<'a, T> {
             MethodBody::Put(_) => "PUT",
             MethodBody::Patch(_) => "PATCH",
             MethodBody::Head => "HEAD",
+            MethodBody::Options => "OPTIONS",
+            MethodBody::Trace => "TRACE",
+            MethodBody::Custom(method) => method,
         }
     }
 }
 
-impl <'a, T: Serialize> MethodBody<'a, T> {
-    pub fn as_body<FORMAT: Format>(&self) -> Result<Option<JsValue>, FetchError> {
-        let body: Option<String> = match self {
+impl <'a, T> MethodBody<'a, T> {
+    /// Gets the data to send as the body, if the method has one.
+    pub(crate) fn data(&self) -> Option<&'a T> {
+        match self {
             MethodBody::Get
             | MethodBody::Delete
-            | MethodBody::Head => None,
+            | MethodBody::Head
+            | MethodBody::Options
+            | MethodBody::Trace
+            | MethodBody::Custom(_) => None,
             MethodBody::Put(data)
             | MethodBody::Post(data)
-            | MethodBody::Patch(data) => {
-                let body = FORMAT::serialize(data)
-                    .ok_or_else(|| FetchError::CouldNotSerializeRequestBody)?;
-                Some(body)
-            }
+            | MethodBody::Patch(data) => Some(data)
+        }
+    }
+}
+
+impl <'a, T: Serialize> MethodBody<'a, T> {
+    pub fn as_body<FORMAT: Format>(&self) -> Result<Option<JsValue>, FetchError> {
+        let data = match self.data() {
+            Some(data) => data,
+            None => return Ok(None),
         };
 
-        let body = body
-            .map(|data| JsValue::from_str(data.as_str()));
-        Ok(body)
+        let body = match FORMAT::TRANSPORT {
+            Transport::Text => FORMAT::serialize(data)
+                .map(|body| JsValue::from_str(body.as_str())),
+            Transport::Binary => FORMAT::serialize_bytes(data)
+                .map(|body| Uint8Array::from(body.as_slice()).into()),
+        };
+        body.map(Some)
+            .ok_or(FetchError::CouldNotSerializeRequestBody)
     }
 }
 
-// TODO, this is only works with String/&str. It would be a good addition if  Vec<u8>/&[u8] were supported.
+/// How data is carried in the bodies of requests and responses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transport {
+    /// The body is a string.
+    Text,
+    /// The body is raw bytes.
+    Binary,
+}
+
 /// Determines what format the data will be transmitted in.
+///
+/// Text-based formats only need to implement `serialize` and `deserialize`.
+/// Binary formats should set `TRANSPORT` to `Transport::Binary` and implement `serialize_bytes` and `deserialize_bytes`.
 pub trait Format {
+    /// How the serialized data is carried in the request and response bodies.
+    const TRANSPORT: Transport = Transport::Text;
+
     fn serialize<T: Serialize>(t: &T) -> Option<String>;
-    fn deserialize<T: DeserializeOwned>(s: &str) -> Option<T>;
+
+    /// Deserializes the text, returning a message describing the problem if it fails.
+    fn deserialize<T: DeserializeOwned>(s: &str) -> Result<T, String>;
+
+    /// The media type of request bodies serialized with this format.
+    ///
+    /// It is sent as the `Content-Type` header of requests that have a body,
+    /// unless the request's `headers` provide one.
+    ///
+    /// By default, this is `text/plain;charset=UTF-8` for text, and `application/octet-stream` for binary data.
+    fn content_type() -> &'static str {
+        match Self::TRANSPORT {
+            Transport::Text => "text/plain;charset=UTF-8",
+            Transport::Binary => "application/octet-stream",
+        }
+    }
+
+    /// Serializes to bytes, used when the transport is binary.
+    ///
+    /// By default, this encodes the output of `serialize` as UTF-8.
+    fn serialize_bytes<T: Serialize>(t: &T) -> Option<Vec<u8>> {
+        Self::serialize(t).map(String::into_bytes)
+    }
+
+    /// Deserializes from bytes, used when the transport is binary.
+    ///
+    /// By default, this decodes the bytes as UTF-8 and passes them to `deserialize`.
+    fn deserialize_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+        Self::deserialize(std::str::from_utf8(bytes).map_err(|err| err.to_string())?)
+    }
 }
 
 /// Transport data using the JSON format
 pub struct Json;
 impl Format for Json {
-//    type Transport = Text;
-
     fn serialize<T: Serialize>(t: &T) -> Option<String> {
         serde_json::to_string(t).ok()
     }
 
-    fn deserialize<T: DeserializeOwned>(s: &str) -> Option<T> {
-        serde_json::from_str(s).ok()
+    fn deserialize<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+        serde_json::from_str(s).map_err(|err| err.to_string())
+    }
+
+    fn content_type() -> &'static str {
+        "application/json"
+    }
+}
+
+/// Transport data as plain text, without any encoding.
+///
+/// This can only serialize and deserialize strings,
+/// so the request and response bodies should be `String`s.
+///
+/// # Example
+/// ```
+///# use yewtil::fetch::{Format, PlainText};
+/// assert_eq!(PlainText::deserialize::<String>("hello"), Ok("hello".to_string()));
+/// assert_eq!(PlainText::serialize(&"hello"), Some("hello".to_string()));
+/// assert_eq!(PlainText::serialize(&5), None);
+/// ```
+pub struct PlainText;
+impl Format for PlainText {
+    fn serialize<T: Serialize>(t: &T) -> Option<String> {
+        match serde_json::to_value(t).ok()? {
+            serde_json::Value::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    fn deserialize<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+        let deserializer: StringDeserializer<serde::de::value::Error> = s.to_string().into_deserializer();
+        T::deserialize(deserializer).map_err(|err| err.to_string())
     }
 }
 
@@ -118,8 +214,9 @@ impl Format for Json {
 ///
 ///     fn headers(&self) -> Vec<(String, String)> {
 ///         // Always attach the same headers.
+///         // The Content-Type of requests with a body is set by the format, so it doesn't need to be included.
 ///         vec![
-///             ("Content-Type".to_string(), "application/json".to_string())
+///             ("Accept".to_string(), "application/json".to_string())
 ///         ]
 ///     }
 /// }
@@ -138,6 +235,27 @@ impl Format for Json {
 /// let fetch_wrapper = Fetch::new(LocalWrapper(ApplesRequest));
 /// fetch_wrapper.fetch(|_| Msg::Variant); // Kicks off an async request.
 /// ```
+///
+/// Simple requests that are sent without a body can instead derive `FetchRequest`,
+/// describing the request using `#[fetch(...)]` attributes:
+///
+/// ```
+///# use serde::Deserialize;
+///# use yewtil::fetch::FetchRequest;
+///# #[derive(Deserialize)]
+///# pub struct Apple;
+/// #[derive(FetchRequest)]
+/// #[fetch(url = "http://some_host_website.com/apples", method = "GET", response = "Vec<Apple>")]
+/// pub struct ApplesRequest {
+///     #[fetch(query)]
+///     page: usize,
+///     #[fetch(query)]
+///     variety: Option<String>,
+/// }
+///
+/// let request = ApplesRequest { page: 2, variety: None };
+/// assert_eq!(request.query(), vec![("page".to_string(), "2".to_string())]);
+/// ```
 pub trait FetchRequest {
     /// The Request Body (if any).
     type RequestBody: Serialize;
@@ -156,71 +274,145 @@ pub trait FetchRequest {
     /// The HTTP method and body (if any) to be used in constructing the request.
     fn method(&self) -> MethodBody<Self::RequestBody>;
 
-    /// The headers to attach to the request .
+    /// The headers to attach to the request.
+    ///
+    /// If the request has a body, a `Content-Type` header using the format's `content_type`
+    /// is attached as well, unless one is provided here.
     fn headers(&self) -> Vec<(String, String)>;
 
     /// Use CORS for the request. By default, it will not.
     fn use_cors(&self) -> bool {
         false
     }
-}
 
-pub fn create_request<T: FetchRequest>(request: &T) -> Result<Request, FetchError> {
-    let method = request.method();
-    let headers = request.headers();
-    let headers = JsValue::from_serde(&headers).expect("Convert Headers to Tuple");
+    /// Whether cookies and HTTP authentication should be sent with the request.
+    ///
+    /// By default, they are only sent to the same origin.
+    /// Use `RequestCredentials::Include` to send them with cross-origin requests.
+    fn credentials(&self) -> RequestCredentials {
+        RequestCredentials::SameOrigin
+    }
+
+    /// How redirects should be handled. By default, they will be followed.
+    fn redirect(&self) -> RequestRedirect {
+        RequestRedirect::Follow
+    }
+
+    /// How the request interacts with the browser's HTTP cache.
+    ///
+    /// By default, the browser decides, which typically means using cached responses while they are fresh.
+    /// Use `RequestCache::NoStore` or `RequestCache::ForceCache` to control offline behavior.
+    fn cache(&self) -> RequestCache {
+        RequestCache::Default
+    }
 
-    // configure options for the request
-    let mut opts = RequestInit::new();
-    opts.method(method.as_method());
-    opts.body(method.as_body::<T::Format>()?.as_ref());
-    opts.headers(&headers);
+    /// Names of response headers that will be captured in the `HeaderStore`,
+    /// to be sent along with subsequent requests that also capture them.
+    ///
+    /// By default, no headers are captured.
+    fn capture_headers(&self) -> &[&str] {
+        &[]
+    }
 
-    // TODO, see if there are more options that can be specified.
-    if request.use_cors() {
-        opts.mode(RequestMode::Cors);
+    /// Query parameters to append to the URL.
+    ///
+    /// Names and values are percent-encoded, so they may contain any characters.
+    /// By default, there are none.
+    fn query(&self) -> Vec<(String, String)> {
+        vec![]
     }
 
-    // Create the request
-    Request::new_with_str_and_init(
-        &request.url(),
-        &opts,
-    )
-        .map_err(|e| FetchError::CouldNotCreateRequest(e)) // TODO make this a Rust value instead.
+    /// How long to wait for the request to complete before giving up with `FetchError::Timeout`.
+    ///
+    /// By default, the request will wait indefinitely.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
 
+/// Gets the headers to attach to the request,
+/// including any stored headers that the request has opted into echoing.
+pub(crate) fn request_headers<T: FetchRequest>(request: &T) -> Vec<(String, String)> {
+    let mut headers = request.headers();
+    HeaderStore::inject(&mut headers, request.capture_headers());
+    let has_content_type = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("Content-Type"));
+    if request.method().data().is_some() && !has_content_type {
+        headers.push(("Content-Type".to_string(), T::Format::content_type().to_string()));
+    }
+    headers
 }
 
-/// Fetch a resource, returning a result of the expected response,
-/// or an error indicating what went wrong.
-pub async fn fetch_resource<T: FetchRequest>(request: Result<Request, FetchError>, _req_type: PhantomData<T>) -> Result<T::ResponseBody, FetchError> {
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct AuthenticatedRequest;
+    impl FetchRequest for AuthenticatedRequest {
+        type RequestBody = ();
+        type ResponseBody = ();
+        type Format = Json;
+
+        fn url(&self) -> String {
+            "http://some_host_website.com/apples".to_string()
+        }
 
-    let request = request?;
-    // Send the request, resolving it to a response.
-    let window: Window = web_sys::window().unwrap();
-    let resp_value = JsFuture::from(window.fetch_with_request(&request))
-        .await
-        .map_err(|_| FetchError::CouldNotCreateFetchFuture)?;
-    debug_assert!(resp_value.is_instance_of::<Response>());
-    let resp: Response = resp_value.dyn_into().unwrap();
+        fn method(&self) -> MethodBody<Self::RequestBody> {
+            MethodBody::Get
+        }
 
+        fn headers(&self) -> Vec<(String, String)> {
+            vec![]
+        }
 
-    // Process the response
-    let text = JsFuture::from(resp.text().map_err(|_| FetchError::TextNotAvailable)?)
-        .await
-        .map_err(|_| FetchError::TextNotAvailable)?;
+        fn capture_headers(&self) -> &[&str] {
+            &["X-Auth-Token"]
+        }
+    }
 
-    let text_string = text.as_string().unwrap();
+    #[test]
+    fn captured_header_is_sent_on_next_request() {
+        assert_eq!(request_headers(&AuthenticatedRequest), vec![]);
 
-    // If the response isn't ok, then return an error without trying to deserialize.
-    if !resp.ok() {
-        return Err(FetchError::ResponseError {status_code: resp.status(), response_body: text_string})
+        HeaderStore::set("x-auth-token", "token".to_string());
+        assert_eq!(
+            request_headers(&AuthenticatedRequest),
+            vec![("X-Auth-Token".to_string(), "token".to_string())]
+        );
     }
 
+    /// Request with a body, and the provided headers.
+    struct FormRequest(Vec<(String, String)>, Vec<(String, String)>);
+    impl FetchRequest for FormRequest {
+        type RequestBody = Vec<(String, String)>;
+        type ResponseBody = ();
+        type Format = Json;
+
+        fn url(&self) -> String {
+            "http://some_host_website.com/apples".to_string()
+        }
+
+        fn method(&self) -> MethodBody<Self::RequestBody> {
+            MethodBody::Post(&self.0)
+        }
+
+        fn headers(&self) -> Vec<(String, String)> {
+            self.1.clone()
+        }
+    }
 
-    let deserialized = <T::Format>::deserialize(&text_string)
-        .ok_or_else(|| {
-            FetchError::DeserializeError{error: "".to_string(), content: text_string}
-        })?;
+    #[test]
+    fn format_content_type_is_sent_with_body() {
+        assert_eq!(
+            request_headers(&FormRequest(vec![], vec![])),
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+    }
 
-    Ok(deserialized)
+    #[test]
+    fn provided_content_type_is_not_duplicated() {
+        let headers = vec![("content-type".to_string(), "text/plain".to_string())];
+        assert_eq!(request_headers(&FormRequest(vec![], headers.clone())), headers);
+    }
 }
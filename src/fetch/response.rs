@@ -0,0 +1,91 @@
+#[cfg(any(target_arch = "wasm32", test))]
+use wasm_bindgen::JsCast;
+#[cfg(any(target_arch = "wasm32", test))]
+use web_sys::Headers;
+
+/// A successful response, along with its status code and headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchResponse<T> {
+    /// The deserialized body of the response.
+    pub body: T,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The headers of the response, with names as reported by the browser.
+    pub headers: Vec<(String, String)>,
+}
+
+impl<T> FetchResponse<T> {
+    /// Gets the value of a header, ignoring the case of its name.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchResponse;
+    /// let response = FetchResponse {
+    ///     body: (),
+    ///     status: 201,
+    ///     headers: vec![("location".to_string(), "/apples/1".to_string())]
+    /// };
+    /// assert_eq!(response.header("Location"), Some("/apples/1"));
+    /// assert_eq!(response.header("Content-Length"), None);
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Gets the size of the body in bytes, as reported by the `Content-Length` header.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchResponse;
+    /// let response = FetchResponse {
+    ///     body: (),
+    ///     status: 200,
+    ///     headers: vec![("content-length".to_string(), "1024".to_string())]
+    /// };
+    /// assert_eq!(response.content_length(), Some(1024));
+    /// ```
+    pub fn content_length(&self) -> Option<u64> {
+        self.header("Content-Length").and_then(parse_content_length)
+    }
+}
+
+/// Parses the value of a `Content-Length` header, ignoring values that aren't a number of bytes.
+pub(crate) fn parse_content_length(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+/// Collects the name-value pairs of the headers.
+#[cfg(any(target_arch = "wasm32", test))]
+pub(crate) fn header_pairs(headers: &Headers) -> Vec<(String, String)> {
+    let entries = match js_sys::try_iter(headers.as_ref()) {
+        Ok(Some(entries)) => entries,
+        _ => return vec![],
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let entry: js_sys::Array = entry.dyn_into().ok()?;
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn content_length_is_parsed() {
+        assert_eq!(parse_content_length("1024"), Some(1024));
+        assert_eq!(parse_content_length(" 12 "), Some(12));
+    }
+
+    #[test]
+    fn invalid_content_length_is_ignored() {
+        assert_eq!(parse_content_length("lots"), None);
+        assert_eq!(parse_content_length("-1"), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::fetch::timeout::sleep;
+use crate::fetch::{fetch_request, FetchError, FetchRequest};
+use std::future::Future;
+use std::time::Duration;
+
+/// Makes a fetch request, retrying it up to `retries` times if it fails in a way that might not
+/// happen again.
+///
+/// Requests are retried when the server responds with a 5xx status code, when the fetch itself
+/// couldn't be made (eg. the connection dropped), or when the request timed out.
+/// Client errors (4xx) and responses that couldn't be deserialized are returned immediately,
+/// because repeating the request won't change the outcome.
+///
+/// The delay before each retry doubles, starting at `base_delay`.
+/// If every attempt fails, the error from the last attempt is returned.
+pub async fn fetch_resource_retry<T: FetchRequest>(
+    request: &T,
+    retries: usize,
+    base_delay: Duration,
+) -> Result<T::ResponseBody, FetchError> {
+    retry(|| fetch_request(request), retries, base_delay, sleep).await
+}
+
+/// Repeats the attempt until it succeeds, fails in a way that isn't retryable, or runs out of retries.
+async fn retry<RES, FUT, SLEEP>(
+    attempt: impl Fn() -> FUT,
+    retries: usize,
+    base_delay: Duration,
+    sleep: impl Fn(Duration) -> SLEEP,
+) -> Result<RES, FetchError>
+where
+    FUT: Future<Output = Result<RES, FetchError>>,
+    SLEEP: Future<Output = ()>,
+{
+    let mut retries_made = 0;
+    loop {
+        match attempt().await {
+            Err(ref err) if retries_made < retries && is_retryable(err) => {
+                sleep(backoff_delay(base_delay, retries_made)).await;
+                retries_made += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Determines if the request might succeed if it is made again.
+fn is_retryable(err: &FetchError) -> bool {
+    match err {
+        FetchError::ResponseError { status_code, .. } => *status_code >= 500,
+        FetchError::CouldNotCreateFetchFuture | FetchError::Timeout => true,
+        _ => false,
+    }
+}
+
+/// The delay before the next retry, which doubles with each retry that has already been made.
+fn backoff_delay(base_delay: Duration, retries_made: usize) -> Duration {
+    let factor = 2u32.saturating_pow(retries_made.min(u32::MAX as usize) as u32);
+    base_delay
+        .checked_mul(factor)
+        .unwrap_or_else(|| Duration::from_secs(u64::MAX))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::future::ready;
+    use std::cell::{Cell, RefCell};
+
+    fn server_error() -> FetchError {
+        FetchError::ResponseError {
+            status_code: 503,
+            response_body: String::new(),
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn retries_server_errors_with_backoff() {
+        let attempts = Cell::new(0);
+        let delays = RefCell::new(vec![]);
+        let result = block_on(retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                if attempts.get() < 3 {
+                    ready(Err(server_error()))
+                } else {
+                    ready(Ok(()))
+                }
+            },
+            5,
+            Duration::from_millis(100),
+            |delay| {
+                delays.borrow_mut().push(delay);
+                ready(())
+            },
+        ));
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(
+            *delays.borrow(),
+            vec![Duration::from_millis(100), Duration::from_millis(200)]
+        );
+    }
+
+    #[test]
+    fn returns_last_error_when_out_of_retries() {
+        let attempts = Cell::new(0);
+        let result: Result<(), FetchError> = block_on(retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                if attempts.get() < 3 {
+                    ready(Err(FetchError::CouldNotCreateFetchFuture))
+                } else {
+                    ready(Err(server_error()))
+                }
+            },
+            2,
+            Duration::from_millis(100),
+            |_| ready(()),
+        ));
+
+        assert_eq!(result, Err(server_error()));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn does_not_retry_client_errors() {
+        let attempts = Cell::new(0);
+        let client_error = FetchError::ResponseError {
+            status_code: 404,
+            response_body: String::new(),
+            headers: vec![],
+        };
+        let result: Result<(), FetchError> = block_on(retry(
+            || {
+                attempts.set(attempts.get() + 1);
+                ready(Err(client_error.clone()))
+            },
+            2,
+            Duration::from_millis(100),
+            |_| ready(()),
+        ));
+
+        assert_eq!(result, Err(client_error));
+        assert_eq!(attempts.get(), 1);
+    }
+}
@@ -1,22 +1,148 @@
 use crate::fetch::FetchError;
+use std::time::Duration;
 
 /// Holds the state of the request being made and response
 /// (if any has been made successfully at any prior point).
+///
+/// The error type is only changed from `FetchError` when the state is borrowed,
+/// in which case it holds a `&FetchError`.
 #[derive(Clone, Debug, PartialEq)]
-pub enum FetchState<RES> {
+pub enum FetchState<RES, ERR = FetchError> {
     NotFetching(Option<RES>),
     Fetching(Option<RES>),
     Fetched(RES),
-    Failed(Option<RES>, FetchError)
+    Failed(Option<RES>, ERR)
 }
 
-impl <RES> Default for FetchState<RES> {
+impl <RES, ERR> Default for FetchState<RES, ERR> {
     fn default() -> Self {
         FetchState::NotFetching(None)
     }
 }
 
-impl <RES> FetchState<RES> {
+impl <RES, ERR> FetchState<RES, ERR> {
+
+    /// Returns true if a request is in flight, regardless of whether a previous response is present.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchState;
+    /// let fetching: FetchState<i32> = FetchState::Fetching(Some(5));
+    /// let fetched: FetchState<i32> = FetchState::Fetched(5);
+    /// assert!(fetching.is_fetching());
+    /// assert!(!fetched.is_fetching());
+    /// ```
+    pub fn is_fetching(&self) -> bool {
+        matches!(self, FetchState::Fetching(_))
+    }
+
+    /// Returns true if the most recent request completed successfully.
+    pub fn is_fetched(&self) -> bool {
+        matches!(self, FetchState::Fetched(_))
+    }
+
+    /// Returns true if the most recent request failed, regardless of whether a previous response is present.
+    pub fn is_failed(&self) -> bool {
+        matches!(self, FetchState::Failed(_, _))
+    }
+
+    /// Returns true if no request is in flight and none has completed since the state was last reset.
+    pub fn is_not_fetching(&self) -> bool {
+        matches!(self, FetchState::NotFetching(_))
+    }
+
+    /// Returns true if a request is in flight and has been for at least `delay`.
+    ///
+    /// Rendering the loading UI only once this is true keeps it from flickering for responses that arrive quickly.
+    /// `fetching_for` is the time elapsed since the state entered `Fetching`.
+    /// `Fetch::should_show_loading` records the start time itself, and should be preferred
+    /// unless the state is held outside of a `Fetch`.
+    ///
+    /// Nothing re-renders the component when `delay` elapses, so it should schedule an update for then,
+    /// such as by sending a message from a timeout.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchState;
+    ///# use std::time::Duration;
+    /// let delay = Duration::from_millis(200);
+    /// let state: FetchState<i32> = FetchState::Fetching(None);
+    /// assert!(!state.should_show_loading(Duration::from_millis(50), delay));
+    /// assert!(state.should_show_loading(Duration::from_millis(250), delay));
+    ///
+    /// let state: FetchState<i32> = FetchState::Fetched(5);
+    /// assert!(!state.should_show_loading(Duration::from_millis(250), delay));
+    /// ```
+    pub fn should_show_loading(&self, fetching_for: Duration, delay: Duration) -> bool {
+        self.is_fetching() && fetching_for >= delay
+    }
+
+    /// Transforms the response (if any) held by the state, preserving the variant.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchState;
+    /// let state: FetchState<i32> = FetchState::Fetching(Some(5));
+    /// assert_eq!(state.map(|res| res.to_string()), FetchState::Fetching(Some("5".to_string())));
+    /// ```
+    pub fn map<U>(self, f: impl FnOnce(RES) -> U) -> FetchState<U, ERR> {
+        match self {
+            FetchState::NotFetching(res) => FetchState::NotFetching(res.map(f)),
+            FetchState::Fetching(res) => FetchState::Fetching(res.map(f)),
+            FetchState::Fetched(res) => FetchState::Fetched(f(res)),
+            FetchState::Failed(res, err) => FetchState::Failed(res.map(f), err),
+        }
+    }
+
+    /// Transforms the error held by a `Failed` state, leaving the response (if any) and other states unchanged.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::{FetchError, FetchState};
+    /// let state: FetchState<()> = FetchState::Failed(None, FetchError::Timeout);
+    /// assert_eq!(
+    ///     state.map_err(|err| err.to_string()),
+    ///     FetchState::Failed(None, FetchError::Timeout.to_string())
+    /// );
+    /// ```
+    pub fn map_err<E2>(self, f: impl FnOnce(ERR) -> E2) -> FetchState<RES, E2> {
+        match self {
+            FetchState::NotFetching(res) => FetchState::NotFetching(res),
+            FetchState::Fetching(res) => FetchState::Fetching(res),
+            FetchState::Fetched(res) => FetchState::Fetched(res),
+            FetchState::Failed(res, err) => FetchState::Failed(res, f(err)),
+        }
+    }
+
+    /// Moves the response (if any) out of the state.
+    ///
+    /// `NotFetching`, `Fetching` and `Failed` keep their variant, with `None` left in place of the response,
+    /// so a previous response can be taken while a request is in flight.
+    /// `Fetched` can't be left without a response, so it is reset to `NotFetching(None)`,
+    /// which means that `is_fetched` will no longer return true.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchState;
+    /// let mut state: FetchState<i32> = FetchState::Fetching(Some(5));
+    /// assert_eq!(state.take_and_reset(), Some(5));
+    /// assert_eq!(state, FetchState::Fetching(None));
+    ///
+    /// let mut state: FetchState<i32> = FetchState::Fetched(5);
+    /// assert_eq!(state.take_and_reset(), Some(5));
+    /// assert_eq!(state, FetchState::NotFetching(None));
+    /// ```
+    pub fn take_and_reset(&mut self) -> Option<RES> {
+        match self {
+            FetchState::NotFetching(res)
+            | FetchState::Fetching(res)
+            | FetchState::Failed(res, _) => res.take(),
+            FetchState::Fetched(_) => match std::mem::take(self) {
+                FetchState::Fetched(res) => Some(res),
+                _ => unreachable!("The state was just matched as Fetched"),
+            },
+        }
+    }
 
     /// Determines if there is a different discriminant between the fetch states.
     pub(crate) fn discriminant_differs(&self, other: &Self) -> bool {
@@ -74,7 +200,7 @@ impl <RES> FetchState<RES> {
         }
     }
 
-    pub(crate) fn failed(self, err: FetchError) -> Self {
+    pub(crate) fn failed(self, err: ERR) -> Self {
         match self {
             FetchState::NotFetching(res) => {
                 FetchState::Failed(res, err)
@@ -90,4 +216,31 @@ impl <RES> FetchState<RES> {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+impl <RES, ERR> FetchState<RES, ERR>
+where
+    for<'a> &'a RES: IntoIterator,
+{
+    /// Iterates over the items of a `Fetched` response, yielding nothing for any other state.
+    ///
+    /// This allows a fetched list to be rendered without matching on the state.
+    /// Responses kept from a previous request by the other states are not included.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::fetch::FetchState;
+    /// let fetched: FetchState<Vec<i32>> = FetchState::Fetched(vec![1, 2, 3]);
+    /// assert_eq!(fetched.iter().sum::<i32>(), 6);
+    ///
+    /// let fetching: FetchState<Vec<i32>> = FetchState::Fetching(Some(vec![1, 2, 3]));
+    /// assert_eq!(fetching.iter().count(), 0);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = <&RES as IntoIterator>::Item> {
+        let response = match self {
+            FetchState::Fetched(res) => Some(res),
+            _ => None,
+        };
+        response.into_iter().flatten()
+    }
+}
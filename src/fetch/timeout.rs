@@ -0,0 +1,85 @@
+use crate::fetch::FetchError;
+use crate::future::as_millis;
+use futures::future::{self, Either};
+use js_sys::Promise;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use wasm_bindgen_futures::JsFuture;
+
+/// Resolves to the result of the fetch, or `FetchError::Timeout` if it takes longer than the timeout.
+pub(crate) async fn with_timeout<T>(
+    fetch: impl Future<Output = Result<T, FetchError>>,
+    timeout: Option<Duration>,
+) -> Result<T, FetchError> {
+    let duration = match timeout {
+        Some(duration) => duration,
+        None => return fetch.await,
+    };
+    let timer = Timer::new(duration);
+    futures::pin_mut!(fetch);
+    match future::select(fetch, timer).await {
+        // The timer is dropped here, clearing its callback.
+        Either::Left((result, _timer)) => result,
+        Either::Right(_) => Err(FetchError::Timeout),
+    }
+}
+
+/// Resolves once the duration has elapsed.
+pub(crate) fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    Timer::new(duration)
+}
+
+/// A future backed by `setTimeout` that resolves once the duration has elapsed.
+///
+/// The timeout is cleared when the timer is dropped, so it won't fire after it is no longer needed.
+struct Timer {
+    handle: i32,
+    elapsed: JsFuture,
+}
+
+impl Timer {
+    fn new(duration: Duration) -> Self {
+        let window = web_sys::window().unwrap();
+        let mut handle = 0;
+        let promise = Promise::new(&mut |resolve, _reject| {
+            handle = window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, as_millis(duration))
+                .expect("setTimeout should be available");
+        });
+        Timer {
+            handle,
+            elapsed: JsFuture::from(promise),
+        }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.elapsed).poll(cx).map(|_| ())
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_timeout_with_handle(self.handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::future::ready;
+
+    #[test]
+    fn without_timeout_resolves_to_fetch_result() {
+        let result = block_on(with_timeout(ready(Ok(5)), None));
+        assert_eq!(result, Ok(5));
+    }
+}
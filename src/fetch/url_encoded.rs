@@ -0,0 +1,54 @@
+use crate::fetch::Format;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Transport data using the `application/x-www-form-urlencoded` format, as used by HTML forms.
+///
+/// Request bodies should be structs or sequences of key-value pairs, whose values are primitives.
+/// Nested structures can't be represented, so serializing them will fail.
+///
+/// # Example
+/// ```
+///# use yewtil::fetch::{Format, UrlEncoded};
+///# use serde::Serialize;
+/// #[derive(Serialize)]
+/// struct Login {
+///     user: String,
+///     remember: bool,
+/// }
+///
+/// let login = Login { user: "jane doe".to_string(), remember: true };
+/// assert_eq!(UrlEncoded::serialize(&login), Some("user=jane+doe&remember=true".to_string()));
+/// ```
+pub struct UrlEncoded;
+impl Format for UrlEncoded {
+    fn serialize<T: Serialize>(t: &T) -> Option<String> {
+        serde_urlencoded::to_string(t).ok()
+    }
+
+    fn deserialize<T: DeserializeOwned>(s: &str) -> Result<T, String> {
+        serde_urlencoded::from_str(s).map_err(|err| err.to_string())
+    }
+
+    fn content_type() -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_pairs() {
+        let pairs = vec![("name".to_string(), "a&b".to_string())];
+        let body = UrlEncoded::serialize(&pairs).expect("Should serialize");
+        assert_eq!(body, "name=a%26b");
+        assert_eq!(UrlEncoded::deserialize::<Vec<(String, String)>>(&body), Ok(pairs));
+    }
+
+    #[test]
+    fn nested_values_cannot_be_serialized() {
+        assert_eq!(UrlEncoded::serialize(&vec![("list", vec![1, 2])]), None);
+    }
+}
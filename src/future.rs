@@ -1,4 +1,12 @@
+use futures::{Stream, StreamExt};
+use futures::channel::mpsc;
+use futures::future::{AbortHandle, Abortable};
+use js_sys::Function;
 use std::future::Future;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use yew::{ComponentLink, Component, agent::{AgentLink, Agent}};
 use stdweb::spawn_local;
 
@@ -13,10 +21,39 @@ pub trait LinkFuture {
     /// If the future panics, then the promise will not resolve, and will leak.
     fn send_future<F>(&self, future: F) where F: Future<Output = Self::Message> + 'static;
 
+    /// Processes a Future that returns a message, like `send_future`, but returns a handle
+    /// that can be used to cancel it.
+    ///
+    /// Once the handle is cancelled or dropped, the message won't be sent, even if the future completes.
+    /// Storing the handle in the component ensures that messages aren't sent after it has been destroyed.
+    fn send_future_cancelable<F>(&self, future: F) -> SpawnHandle where F: Future<Output = Self::Message> + 'static;
+
     /// Registers a future that resolves to multiple messages.
     /// # Panics
     /// If the future panics, then the promise will not resolve, and will leak.
     fn send_future_batch<F>(&self, future: F) where F: Future<Output=Vec<Self::Message>> + 'static;
+
+    /// Registers a stream, sending a message back to the component's loop for every item it yields.
+    ///
+    /// Messages stop being sent once the stream ends.
+    fn send_stream<S>(&self, stream: S) where S: Stream<Item=Self::Message> + 'static;
+
+    /// Sends a message created by `f` every `period`, until the returned handle is dropped.
+    ///
+    /// The interval is set on the global scope, so this works for agents running in web workers,
+    /// as well as for components.
+    ///
+    /// # Panics
+    /// If the interval can't be set, this will panic.
+    fn send_interval<F>(&self, period: Duration, f: F) -> IntervalHandle where F: Fn() -> Self::Message + 'static {
+        let (sender, ticks) = mpsc::unbounded();
+        let handle = IntervalHandle::new(period, move || {
+            let _ = sender.unbounded_send(());
+        });
+        // The stream ends once the handle drops the callback, and with it the sender.
+        self.send_stream(ticks.map(move |_| f()));
+        handle
+    }
 }
 
 impl <COMP: Component> LinkFuture for ComponentLink<COMP> {
@@ -32,6 +69,19 @@ impl <COMP: Component> LinkFuture for ComponentLink<COMP> {
 
     }
 
+    fn send_future_cancelable<F>(&self, future: F) -> SpawnHandle where F: Future<Output=Self::Message> + 'static {
+        let mut link: ComponentLink<COMP> = self.clone();
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        let future = Abortable::new(future, registration);
+        let js_future = async move {
+            if let Ok(message) = future.await {
+                link.send_message(message);
+            }
+        };
+        spawn_local(js_future);
+        SpawnHandle { abort_handle }
+    }
+
     fn send_future_batch<F>(&self, future: F) where F: Future<Output=Vec<Self::Message>> + 'static {
         let mut link: ComponentLink<COMP> = self.clone();
         let js_future = async move {
@@ -40,6 +90,17 @@ impl <COMP: Component> LinkFuture for ComponentLink<COMP> {
         };
         spawn_local(js_future);
     }
+
+    fn send_stream<S>(&self, stream: S) where S: Stream<Item=Self::Message> + 'static {
+        let mut link: ComponentLink<COMP> = self.clone();
+        let js_future = async move {
+            let mut stream = Box::pin(stream);
+            while let Some(message) = stream.next().await {
+                link.send_message(message);
+            }
+        };
+        spawn_local(js_future);
+    }
 }
 
 impl <AGN: Agent> LinkFuture for AgentLink<AGN> {
@@ -55,7 +116,128 @@ impl <AGN: Agent> LinkFuture for AgentLink<AGN> {
         spawn_local(js_future);
     }
 
-    fn send_future_batch<F>(&self, _future: F) where F: Future<Output=Vec<Self::Message>> + 'static {
-        unimplemented!("Agents don't support batching their messages.")
+    fn send_future_cancelable<F>(&self, future: F) -> SpawnHandle where F: Future<Output=Self::Message> + 'static {
+        let link: AgentLink<AGN> = self.clone();
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        let future = Abortable::new(future, registration);
+        let js_future = async move {
+            if let Ok(message) = future.await {
+                let cb = link.callback(|m: AGN::Message| m);
+                cb.emit(message);
+            }
+        };
+        spawn_local(js_future);
+        SpawnHandle { abort_handle }
+    }
+
+    fn send_future_batch<F>(&self, future: F) where F: Future<Output=Vec<Self::Message>> + 'static {
+        let link: AgentLink<AGN> = self.clone();
+        let js_future = async move {
+            let messages: Vec<AGN::Message> = future.await;
+            let cb = link.callback(|m: AGN::Message| m);
+            for message in messages {
+                cb.emit(message);
+            }
+        };
+        spawn_local(js_future);
+    }
+
+    fn send_stream<S>(&self, stream: S) where S: Stream<Item=Self::Message> + 'static {
+        let link: AgentLink<AGN> = self.clone();
+        let js_future = async move {
+            let cb = link.callback(|m: AGN::Message| m);
+            let mut stream = Box::pin(stream);
+            while let Some(message) = stream.next().await {
+                cb.emit(message);
+            }
+        };
+        spawn_local(js_future);
+    }
+}
+
+/// A handle to a future that was registered using `LinkFuture::send_future_cancelable`.
+///
+/// Dropping the handle cancels the future.
+#[derive(Debug)]
+pub struct SpawnHandle {
+    abort_handle: AbortHandle,
+}
+
+impl SpawnHandle {
+    /// Cancels the future, preventing its message from being sent.
+    pub fn cancel(&self) {
+        self.abort_handle.abort()
+    }
+}
+
+impl Drop for SpawnHandle {
+    fn drop(&mut self) {
+        self.cancel()
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// The global scope, which is a `Window` on the main thread, or a `WorkerGlobalScope` in a web worker.
+    /// Both of them provide `setInterval` and `clearInterval`.
+    type GlobalScope;
+
+    #[wasm_bindgen(method, catch, js_name = setInterval)]
+    fn set_interval(this: &GlobalScope, handler: &Function, timeout: i32) -> Result<i32, JsValue>;
+
+    #[wasm_bindgen(method, js_name = clearInterval)]
+    fn clear_interval(this: &GlobalScope, handle: i32);
+}
+
+fn global_scope() -> GlobalScope {
+    js_sys::global().unchecked_into()
+}
+
+/// A handle to a callback that is called repeatedly using `setInterval`,
+/// such as for the messages sent by `LinkFuture::send_interval`.
+///
+/// Dropping the handle clears the interval, so the callback won't be called again.
+pub struct IntervalHandle {
+    interval_handle: i32,
+    _callback: Closure<dyn FnMut()>,
+}
+
+impl IntervalHandle {
+    /// Calls the callback every `period` until the handle is dropped.
+    ///
+    /// # Panics
+    /// If the interval can't be set, this will panic.
+    pub(crate) fn new(period: Duration, callback: impl FnMut() + 'static) -> Self {
+        let callback = Closure::wrap(Box::new(callback) as Box<dyn FnMut()>);
+        let interval_handle = global_scope()
+            .set_interval(callback.as_ref().unchecked_ref(), as_millis(period))
+            .expect("setInterval should be available");
+        IntervalHandle {
+            interval_handle,
+            _callback: callback,
+        }
+    }
+}
+
+impl Drop for IntervalHandle {
+    fn drop(&mut self) {
+        global_scope().clear_interval(self.interval_handle);
+    }
+}
+
+/// Converts the duration to the milliseconds expected by `setTimeout` and `setInterval`,
+/// saturating if it is too long.
+pub(crate) fn as_millis(duration: Duration) -> i32 {
+    duration.as_millis().min(i32::MAX as u128) as i32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn long_durations_saturate() {
+        assert_eq!(as_millis(Duration::from_millis(1500)), 1500);
+        assert_eq!(as_millis(Duration::from_secs(u64::MAX)), i32::MAX);
     }
 }
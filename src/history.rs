@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 use std::ops::Deref;
 
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
 // TODO when const generics lands, it would be useful to add a usize type parameter over the max number of elements.
 
 // It would also be interesting to see if a diffing history implementation could be built over types
@@ -8,20 +11,74 @@ use std::ops::Deref;
 // and the transitions can be rolled back.
 // That would probably have worse performance in exchange for smaller size.
 
+/// Trait for values that can describe how they differ from another value.
+///
+/// This allows a `History` to produce a changelog of its entries using `History::deltas`.
+#[cfg(feature = "diff")]
+pub trait Diff {
+    /// Description of the changes between two values.
+    type Delta;
+
+    /// Describes the changes needed to turn `self` into `other`.
+    fn diff(&self, other: &Self) -> Self::Delta;
+}
+
 /// Wrapper that keeps track of prior values that have been assigned to it.
 ///
 /// It keeps values that have been `set` for it around for the duration of its lifetime,
 /// or until they are dropped by calling `reset` or `forget`.
 ///
 /// Prior values can be iterated over as well.
-pub struct History<T>(VecDeque<T>);
+///
+/// The current value can be moved back and forth over the stored values using `undo` and `redo`.
+pub struct History<T> {
+    /// The values, ordered from newest to oldest.
+    values: VecDeque<T>,
+    /// The maximum number of values that will be retained, if bounded.
+    max: Option<usize>,
+    /// Index of the current value.
+    ///
+    /// Values in front of the cursor have been undone, and can be restored with `redo`.
+    cursor: usize,
+}
 
 impl<T> History<T> {
     /// Creates a new history wrapper.
     pub fn new(value: T) -> Self {
-        let mut vec = VecDeque::new();
-        vec.push_front(value);
-        Self(vec)
+        let mut values = VecDeque::new();
+        values.push_front(value);
+        Self {
+            values,
+            max: None,
+            cursor: 0,
+        }
+    }
+
+    /// Creates a new history wrapper that will keep at most `max` entries.
+    ///
+    /// Once the limit is reached, setting a new value will drop the oldest entry.
+    /// Because the current value is always kept, a `max` of `0` is treated as `1`.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::with_capacity(0, 2);
+    /// history.set(1);
+    /// history.set(2);
+    ///
+    /// assert_eq!(history.count(), 2);
+    /// history.reset();
+    /// assert_eq!(*history, 1);
+    /// ```
+    pub fn with_capacity(value: T, max: usize) -> Self {
+        let max = max.max(1);
+        let mut values = VecDeque::with_capacity(max);
+        values.push_front(value);
+        Self {
+            values,
+            max: Some(max),
+            cursor: 0,
+        }
     }
 
     /// Set the value represented by the `History` struct.
@@ -29,6 +86,8 @@ impl<T> History<T> {
     /// This pushes the new value into the front of a list,
     /// where the front-most value represents the most recent value.
     ///
+    /// If any values have been undone, they are dropped, so they can no longer be restored with `redo`.
+    ///
     /// # Example
     /// ```
     ///# use yewtil::History;
@@ -39,7 +98,38 @@ impl<T> History<T> {
     /// assert_eq!(history.count(), 2);
     /// ```
     pub fn set(&mut self, value: T) {
-        self.0.push_front(value)
+        self.values.drain(..self.cursor);
+        self.cursor = 0;
+        self.values.push_front(value);
+        if let Some(max) = self.max {
+            self.values.truncate(max);
+        }
+    }
+
+    /// Will only `set` the value if the predicate, given the current and provided values, returns true.
+    ///
+    /// This allows deciding what counts as a change worth keeping in the history,
+    /// like `neq_set` does for values that aren't equal.
+    ///
+    /// It returns true to indicate if the history's current value was updated to be the provided value.
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let differs_in_content = |current: &String, new: &String| current.trim() != new.trim();
+    ///
+    /// let mut history = History::new("text".to_string());
+    /// assert!(!history.set_if("text ".to_string(), differs_in_content));
+    /// assert!(history.set_if("text!".to_string(), differs_in_content));
+    ///
+    /// assert_eq!(history.count(), 2);
+    /// ```
+    pub fn set_if(&mut self, value: T, predicate: impl Fn(&T, &T) -> bool) -> bool {
+        if predicate(self.as_ref(), &value) {
+            self.set(value);
+            true
+        } else {
+            false
+        }
     }
 
     /// Replaces the current value without creating a history entry.
@@ -54,12 +144,86 @@ impl<T> History<T> {
     /// assert_eq!(history.count(), 1);
     /// ```
     pub fn replace(&mut self, value: T) {
-        self.0[0] = value;
+        self.values[self.cursor] = value;
+    }
+
+    /// Moves the current value back to the previous entry, without dropping any entries.
+    ///
+    /// The returned bool indicates if there was a previous entry to move to.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    ///
+    /// assert!(history.undo());
+    /// assert_eq!(*history, 0);
+    /// assert_eq!(history.count(), 2);
+    ///
+    /// assert!(!history.undo());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        if self.can_undo() {
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the current value forward to the entry that was most recently undone.
+    ///
+    /// The returned bool indicates if there was an undone entry to move to.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    /// history.undo();
+    ///
+    /// assert!(history.redo());
+    /// assert_eq!(*history, 1);
+    ///
+    /// assert!(!history.redo());
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        if self.can_redo() {
+            self.cursor -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if there is an older entry that `undo` can move to.
+    pub fn can_undo(&self) -> bool {
+        self.cursor + 1 < self.values.len()
+    }
+
+    /// Returns true if there is an undone entry that `redo` can move to.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    /// history.undo();
+    /// assert!(history.can_redo());
+    ///
+    /// history.set(2);
+    /// assert!(!history.can_redo());
+    /// assert_eq!(history.count(), 2);
+    /// ```
+    pub fn can_redo(&self) -> bool {
+        self.cursor > 0
     }
 
     /// Removes all prior values.
     ///
     /// This effectively sets a new "checkpoint" that can be restored by calling `reset`.
+    /// Entries that have been undone are kept, and can still be restored with `redo`.
     ///
     /// The returned bool indicates if any entries were removed.
     ///
@@ -75,8 +239,8 @@ impl<T> History<T> {
     /// assert_eq!(history.count(), 1);
     /// ```
     pub fn forget(&mut self) -> bool {
-        if self.dirty() {
-            self.0.drain(1..);
+        if self.can_undo() {
+            self.values.drain(self.cursor + 1..);
             true
         } else {
             false
@@ -100,13 +264,81 @@ impl<T> History<T> {
     /// ```
     pub fn reset(&mut self) -> bool {
         if self.dirty() {
-            self.0.drain(..self.0.len() - 1);
+            self.values.drain(..self.values.len() - 1);
+            self.cursor = 0;
             true
         } else {
             false
         }
     }
 
+    /// Removes up to `n` entries, starting with the current value, making an older entry the current value.
+    ///
+    /// The oldest entry is never removed.
+    /// Entries that have been undone are newer than the ones being removed, so they are removed as well
+    /// without being counted.
+    ///
+    /// The returned number indicates how many entries were rewound, which is less than `n` if
+    /// there weren't enough older entries.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    /// history.set(2);
+    /// history.set(3);
+    ///
+    /// assert_eq!(history.rewind(2), 2);
+    /// assert_eq!(*history, 1);
+    ///
+    /// assert_eq!(history.rewind(5), 1);
+    /// assert_eq!(*history, 0);
+    /// assert_eq!(history.count(), 1);
+    /// ```
+    pub fn rewind(&mut self, n: usize) -> usize {
+        let rewound = n.min(self.values.len() - self.cursor - 1);
+        self.values.drain(..self.cursor + rewound);
+        self.cursor = 0;
+        rewound
+    }
+
+    /// Removes every entry that doesn't satisfy the predicate, except for the current value,
+    /// which is always kept.
+    ///
+    /// The order of the remaining entries is preserved.
+    /// The returned number indicates how many entries were removed.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    /// history.set(2);
+    /// history.set(3);
+    ///
+    /// let removed = history.retain(|value| value % 2 == 0);
+    /// assert_eq!(removed, 1);
+    /// assert_eq!(*history, 3);
+    /// assert_eq!(history.iter().collect::<Vec<_>>(), vec![&3, &2, &0]);
+    /// ```
+    pub fn retain(&mut self, f: impl Fn(&T) -> bool) -> usize {
+        let count = self.values.len();
+        let cursor = self.cursor;
+        let mut index = 0;
+        let mut removed_before_cursor = 0;
+        self.values.retain(|value| {
+            let keep = index == cursor || f(value);
+            if !keep && index < cursor {
+                removed_before_cursor += 1;
+            }
+            index += 1;
+            keep
+        });
+        self.cursor -= removed_before_cursor;
+        count - self.values.len()
+    }
+
     /// Returns true if there is more than one entry in the history.
     ///
     /// # Example
@@ -135,22 +367,152 @@ impl<T> History<T> {
     /// assert_eq!(history.count(), 2);
     /// ```
     pub fn count(&self) -> usize {
-        self.0.len()
+        self.values.len()
+    }
+
+    /// Gets the entry at the index, where `0` is the newest entry, and `count() - 1` is the oldest.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    ///
+    /// assert_eq!(history.get(0), Some(&1));
+    /// assert_eq!(history.get(1), Some(&0));
+    /// assert_eq!(history.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Gets the oldest entry in the history.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    ///
+    /// assert_eq!(history.oldest(), &0);
+    /// ```
+    pub fn oldest(&self) -> &T {
+        self.values
+            .back()
+            .expect("History should have at least one item")
+    }
+
+    /// Gets the index of the current value, where `0` is the newest entry.
+    ///
+    /// This will only be non-zero after calling `undo`.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    /// assert_eq!(history.current_index(), 0);
+    ///
+    /// history.undo();
+    /// assert_eq!(history.current_index(), 1);
+    /// assert_eq!(history.get(history.current_index()), Some(&0));
+    /// ```
+    pub fn current_index(&self) -> usize {
+        self.cursor
     }
 
     /// Produces an iterator over references to history items ordered from newest to oldest.
+    ///
+    /// This includes entries that have been undone.
     pub fn iter(&self) -> std::collections::vec_deque::Iter<T> {
-        self.0.iter()
+        self.values.iter()
+    }
+
+    /// Folds over every entry in the history, ordered from oldest to newest.
+    ///
+    /// This allows the `History` to be treated as an event log,
+    /// where the present state is derived from all of the events that have been `set`.
+    /// Entries that have been undone are not included.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(1);
+    /// history.set(1);
+    /// history.set(1);
+    ///
+    /// let total = history.folded(0, |total, event| total + event);
+    /// assert_eq!(total, 3);
+    /// ```
+    pub fn folded<S>(&self, init: S, f: impl Fn(S, &T) -> S) -> S {
+        self.values.iter().skip(self.cursor).rev().fold(init, f)
+    }
+
+    /// Produces a new `History` by applying a function to every entry, preserving their order.
+    ///
+    /// The current value of the new `History` is derived from the current value of this one.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(1);
+    /// history.set(2);
+    ///
+    /// let doubled = history.map(|value| value * 2);
+    /// assert_eq!(*doubled, 4);
+    /// assert_eq!(doubled.iter().collect::<Vec<_>>(), vec![&4, &2]);
+    /// ```
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> History<U> {
+        History {
+            values: self.values.iter().map(f).collect(),
+            max: self.max,
+            cursor: self.cursor,
+        }
     }
 
     /// Gets the current value.
     pub fn into_inner(mut self) -> T {
-        self.0
-            .pop_front()
+        self.values
+            .remove(self.cursor)
             .expect("History should have at least one item")
     }
 }
 
+#[cfg(feature = "diff")]
+impl<T: Diff> History<T> {
+    /// Produces an iterator over the changes between consecutive entries, ordered from newest to oldest.
+    ///
+    /// Each delta describes the change from an entry to the one that was `set` after it,
+    /// so a history with `n` entries produces `n - 1` deltas.
+    /// Like `iter`, this includes entries that have been undone.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::{Diff, History};
+    /// struct Text(String);
+    ///
+    /// impl Diff for Text {
+    ///     type Delta = isize;
+    ///
+    ///     fn diff(&self, other: &Self) -> isize {
+    ///         other.0.len() as isize - self.0.len() as isize
+    ///     }
+    /// }
+    ///
+    /// let mut history = History::new(Text("Hi".to_string()));
+    /// history.set(Text("Hello".to_string()));
+    /// history.set(Text("Hell".to_string()));
+    ///
+    /// assert_eq!(history.deltas().collect::<Vec<_>>(), vec![-1, 3]);
+    /// ```
+    pub fn deltas(&self) -> impl Iterator<Item = T::Delta> + '_ {
+        self.values
+            .iter()
+            .zip(self.values.iter().skip(1))
+            .map(|(newer, older)| older.diff(newer))
+    }
+}
+
 impl<T: PartialEq> History<T> {
     /// Will only `set` the value if the provided value is different than the current value.
     ///
@@ -166,13 +528,45 @@ impl<T: PartialEq> History<T> {
     /// assert!(did_set);
     /// ```
     pub fn neq_set(&mut self, value: T) -> bool {
-        if self.0[0] != value {
+        if self.as_ref() != &value {
             self.set(value);
             true
         } else {
             false
         }
     }
+
+    /// Will only set the value if the provided value is different than the current value,
+    /// removing all other entries if it does.
+    ///
+    /// This combines `neq_set` and `forget` so that memory use stays flat.
+    ///
+    /// It returns true to indicate if the history's current value was updated to be the provided value.
+    /// # Example
+    /// ```
+    ///# use yewtil::History;
+    /// let mut history = History::new(0);
+    /// history.set(1);
+    ///
+    /// let did_set = history.neq_set_forget(1);
+    /// assert!(!did_set);
+    /// assert_eq!(history.count(), 2);
+    ///
+    /// let did_set = history.neq_set_forget(2);
+    /// assert!(did_set);
+    /// assert_eq!(*history, 2);
+    /// assert_eq!(history.count(), 1);
+    /// ```
+    pub fn neq_set_forget(&mut self, value: T) -> bool {
+        if self.as_ref() != &value {
+            self.values.clear();
+            self.values.push_front(value);
+            self.cursor = 0;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl<T> IntoIterator for History<T> {
@@ -180,14 +574,14 @@ impl<T> IntoIterator for History<T> {
     type IntoIter = std::collections::vec_deque::IntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.values.into_iter()
     }
 }
 
 impl<T> AsRef<T> for History<T> {
     fn as_ref(&self) -> &T {
-        // Get the first element
-        &self.0[0]
+        // Get the element at the cursor
+        &self.values[self.cursor]
     }
 }
 
@@ -198,3 +592,53 @@ impl<T> Deref for History<T> {
         self.as_ref()
     }
 }
+
+/// Serializes the current value and all prior values, ordered from newest to oldest.
+///
+/// Entries that have been undone are not serialized.
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for History<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.values.iter().skip(self.cursor))
+    }
+}
+
+/// Deserializes a sequence of values ordered from newest to oldest.
+///
+/// Because a `History` always has a current value, an empty sequence is rejected.
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for History<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = VecDeque::<T>::deserialize(deserializer)?;
+        if values.is_empty() {
+            return Err(D::Error::invalid_length(0, &"at least one value"));
+        }
+        Ok(Self {
+            values,
+            max: None,
+            cursor: 0,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use serde::de::value::{Error as ValueError, SeqDeserializer};
+
+    fn deserialize(values: Vec<i32>) -> Result<History<i32>, ValueError> {
+        History::deserialize(SeqDeserializer::<_, ValueError>::new(values.into_iter()))
+    }
+
+    #[test]
+    fn deserialize_restores_values_newest_to_oldest() {
+        let history = deserialize(vec![2, 1, 0]).expect("Should deserialize");
+        assert_eq!(*history, 2);
+        assert_eq!(history.iter().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn deserialize_rejects_empty_sequence() {
+        assert!(deserialize(vec![]).is_err());
+    }
+}
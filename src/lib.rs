@@ -9,10 +9,15 @@
 //! * "mrc_irc" - Ergonomic Rc pointers.
 //! * "lrc" - Linked-list Rc pointer.
 //! * "history" - History tracker
-// //! * "dsl" - Use functions instead of Yew's `html!` macro.
+//! * "diff" - Changelogs of the values in a `History`.
+//! * "serde" - Serialize and Deserialize implementations for supported types.
+//! * "csv" - CSV format for fetch requests (requires "fetch").
+//! * "msgpack" - MessagePack format for fetch requests.
+//! * "urlencoded" - `application/x-www-form-urlencoded` format for fetch requests.
+//! * "dsl" - Use functions instead of Yew's `html!` macro.
 
-//#[cfg(feature = "dsl")]
-//pub mod dsl;
+#[cfg(feature = "dsl")]
+pub mod dsl;
 
 #[cfg(feature = "neq")]
 mod not_equal_assign;
@@ -29,15 +34,18 @@ mod history;
 #[cfg(feature = "history")]
 pub use history::History;
 
+#[cfg(feature = "diff")]
+pub use history::Diff;
+
 #[cfg(feature = "neq")]
-pub use not_equal_assign::NeqAssign;
+pub use not_equal_assign::{neq_assign_indexed, neq_assign_map, NeqAssign, NeqAssignSome};
 
 #[cfg(feature = "pure")]
 pub use pure::{Pure, PureComponent};
 
 
 #[cfg(feature = "pure")]
-pub use yewtil_macro::function_component;
+pub use yewtil_macro::{function_component, Pure};
 
 #[cfg(feature = "fetch")]
 pub mod fetch;
@@ -45,7 +53,7 @@ pub mod fetch;
 #[cfg(feature = "effect")]
 mod effect;
 #[cfg(feature = "effect")]
-pub use effect::{Effect, effect};
+pub use effect::{Effect, EffectComponent, EffectfulComponent, effect};
 
 #[cfg(feature = "future")]
 pub mod future;
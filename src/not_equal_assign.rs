@@ -1,6 +1,8 @@
 //! Module for `neq_assign` utility function.
 
 use std::borrow::BorrowMut;
+use std::collections::HashMap;
+use std::hash::Hash;
 use yew::html::ShouldRender;
 
 /// Blanket trait to provide a convenience method for assigning props in `changed` or updating values in `update`.
@@ -9,6 +11,9 @@ pub trait NeqAssign<NEW> {
     ///
     /// Short for "Not equal assign".
     ///
+    /// Tuples of `PartialEq` values are covered as well, so multiple values can be compared and assigned at once
+    /// by writing `self.pair.neq_assign((a, b))`, which returns true if any element differed.
+    ///
     /// # Example
     /// ```
     /// # use yew::{Component, ShouldRender, ComponentLink};
@@ -57,3 +62,126 @@ impl<T: BorrowMut<U>, U: PartialEq> NeqAssign<U> for T {
         }
     }
 }
+
+/// Trait for assigning values into `Option`s without replacing equal values.
+///
+/// This can't be provided by `NeqAssign`, because its blanket implementation already covers `Option<T>`.
+pub trait NeqAssignSome<T> {
+    /// Assigns `Some(new)` to `self` if it is `None` or holds a value that isn't equal to `new`.
+    ///
+    /// Returns true if `self` was changed.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::NeqAssignSome;
+    /// let mut value = None;
+    /// assert!(value.neq_assign_some(1));
+    /// assert!(!value.neq_assign_some(1));
+    /// assert!(value.neq_assign_some(2));
+    /// assert_eq!(value, Some(2));
+    /// ```
+    fn neq_assign_some(&mut self, new: T) -> ShouldRender;
+}
+
+impl<T: PartialEq> NeqAssignSome<T> for Option<T> {
+    fn neq_assign_some(&mut self, new: T) -> bool {
+        match self {
+            Some(existing) if *existing == new => false,
+            _ => {
+                *self = Some(new);
+                true
+            }
+        }
+    }
+}
+
+/// Inserts `value` into the map under `key`, unless an equal value is already present.
+///
+/// Returns true if the map was changed.
+///
+/// # Example
+/// ```
+///# use std::collections::HashMap;
+///# use yewtil::neq_assign_map;
+/// let mut map = HashMap::new();
+/// assert!(neq_assign_map(&mut map, "a", 1));
+/// assert!(!neq_assign_map(&mut map, "a", 1));
+/// assert!(neq_assign_map(&mut map, "a", 2));
+/// ```
+pub fn neq_assign_map<K: Eq + Hash, V: PartialEq>(map: &mut HashMap<K, V>, key: K, value: V) -> bool {
+    match map.get_mut(&key) {
+        Some(existing) => existing.neq_assign(value),
+        None => {
+            map.insert(key, value);
+            true
+        }
+    }
+}
+
+/// Assigns `new` to `assignee`, returning the indices of the elements that changed.
+///
+/// This allows callers that render large lists to only update the elements that differ,
+/// instead of treating the whole list as changed.
+/// If the lengths differ, the indices that only exist in one of the vectors are included as well.
+///
+/// # Example
+/// ```
+///# use yewtil::neq_assign_indexed;
+/// let mut values = vec![1, 2, 3];
+/// let changed = neq_assign_indexed(&mut values, vec![1, 5, 3, 4]);
+/// assert_eq!(changed, vec![1, 3]);
+/// assert_eq!(values, vec![1, 5, 3, 4]);
+/// ```
+pub fn neq_assign_indexed<T: PartialEq>(assignee: &mut Vec<T>, new: Vec<T>) -> Vec<usize> {
+    let common_len = assignee.len().min(new.len());
+    let max_len = assignee.len().max(new.len());
+    let changed = assignee
+        .iter()
+        .zip(new.iter())
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(index, _)| index)
+        .chain(common_len..max_len)
+        .collect();
+    *assignee = new;
+    changed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tuple_neq_assign_detects_change_in_second_element() {
+        let mut pair = (1, "hello".to_string());
+        let did_change = pair.neq_assign((1, "world".to_string()));
+
+        assert!(did_change);
+        assert_eq!(pair, (1, "world".to_string()));
+    }
+
+    #[test]
+    fn tuple_neq_assign_equal_values() {
+        let mut pair = (1, "hello".to_string());
+        let did_change = pair.neq_assign((1, "hello".to_string()));
+
+        assert!(!did_change);
+    }
+
+    #[test]
+    fn indexed_neq_assign_reports_removed_indices() {
+        let mut values = vec![1, 2, 3, 4];
+        let changed = neq_assign_indexed(&mut values, vec![0, 2]);
+
+        assert_eq!(changed, vec![0, 2, 3]);
+        assert_eq!(values, vec![0, 2]);
+    }
+
+    #[test]
+    fn indexed_neq_assign_equal_values() {
+        let mut values = vec![1, 2, 3];
+        let changed = neq_assign_indexed(&mut values, vec![1, 2, 3]);
+
+        assert!(changed.is_empty());
+    }
+}
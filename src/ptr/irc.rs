@@ -1,13 +1,17 @@
 use crate::ptr::rc_box::{
-    clone_impl, clone_inner, decrement_and_possibly_deallocate, get_count, get_ref_boxed_content,
-    is_exclusive, try_unwrap, unwrap_clone, RcBox,
+    clone_impl, clone_inner, decrement_and_possibly_deallocate, get_count,
+    get_mut_boxed_content, get_ref_boxed_content, is_exclusive, try_unwrap, unwrap_clone,
+    ErasedRcBox, RcBox,
 };
+use crate::ptr::{IrcRef, Mrc, Weak};
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::ptr::NonNull;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Immutable Reference Counted pointer.
 ///
@@ -35,6 +39,39 @@ impl<T> Irc<T> {
         Self { ptr }
     }
 
+    /// Allocates a value that holds a `Weak` pointer to itself.
+    ///
+    /// The closure is given a `Weak` pointer to the allocation before the value exists,
+    /// so the value can store it (eg. as a back-reference from a node to its container).
+    /// Upgrading the `Weak` within the closure will return `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::{Irc, Weak};
+    /// struct Node {
+    ///     this: Weak<Node>,
+    ///     name: String,
+    /// }
+    ///
+    /// let node = Irc::new_cyclic(|this| Node {
+    ///     this: this.clone(),
+    ///     name: "root".to_string(),
+    /// });
+    /// let upgraded = node.this.upgrade().expect("Node should be alive");
+    /// assert_eq!(upgraded.name, "root");
+    /// assert_eq!(node.get_count(), 2);
+    /// ```
+    pub fn new_cyclic<F: FnOnce(&Weak<T>) -> T>(f: F) -> Self {
+        let mut ptr = RcBox::uninit().into_non_null();
+        get_ref_boxed_content(&ptr).inc_weak_count();
+        // If the closure panics, dropping this deallocates the node, as no strong references exist yet.
+        let weak = Weak { ptr };
+        let value = f(&weak);
+        get_mut_boxed_content(&mut ptr).init(value);
+        std::mem::drop(weak);
+        Self { ptr }
+    }
+
     /// Tries to extract the value from the `Irc`, returning the `Irc` if there is one or
     /// more other smart pointers to the value.
     ///
@@ -55,10 +92,41 @@ impl<T> Irc<T> {
         })
     }
 
+    /// Converts this `Irc` into an `Mrc`, returning the `Irc` if there are one or more
+    /// other smart pointers to the value.
+    ///
+    /// This is the counterpart to `Mrc::into_irc`, allowing a value that is no longer shared
+    /// to be modified in place.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::Irc;
+    /// let irc = Irc::new(0);
+    ///
+    /// let clone = irc.clone();
+    /// let irc = irc.try_into_mrc().expect_err("Should not be able to convert a shared Irc");
+    ///
+    /// std::mem::drop(clone);
+    /// let mut mrc = irc.try_into_mrc().expect("Should get an Mrc");
+    /// *mrc = 1;
+    /// assert!(mrc.is_exclusive());
+    /// ```
+    pub fn try_into_mrc(self) -> Result<Mrc<T>, Self> {
+        if is_exclusive(self.ptr) {
+            // Because the Irc is dropped, decrementing the count,
+            // the count needs to be restored here.
+            get_ref_boxed_content(&self.ptr).inc_count();
+            Ok(Mrc { ptr: self.ptr })
+        } else {
+            Err(self)
+        }
+    }
+
     /// Gets the reference count of the `Irc`.
     ///
     /// An exclusive `Irc` will have a count of `1`.
     /// The count is incremented on any cloning action and is decremented when `drop` is called.
+    /// An `IrcRef` created by `map` shares this count.
     ///
     /// # Example
     /// ```
@@ -91,6 +159,37 @@ impl<T> Irc<T> {
     pub fn is_exclusive(&self) -> bool {
         is_exclusive(self.ptr)
     }
+
+    /// Creates an `IrcRef` that points to a part of this `Irc`'s value,
+    /// keeping the whole value alive for as long as the `IrcRef` exists.
+    ///
+    /// This is useful for handing children only the part of a shared value that they depend on,
+    /// so that they can compare it for changes without being affected by unrelated parts.
+    /// The `IrcRef` holds the `Irc`'s reference, so it counts towards `get_count`.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::{Irc, IrcRef};
+    /// struct Config {
+    ///     theme: String,
+    ///     page_size: usize,
+    /// }
+    ///
+    /// let config = Irc::new(Config { theme: "dark".to_string(), page_size: 20 });
+    /// let theme: IrcRef<String> = config.clone().map(|config| &config.theme);
+    /// assert_eq!(*theme, "dark");
+    /// assert_eq!(config.get_count(), 2);
+    ///
+    /// std::mem::drop(config);
+    /// assert_eq!(*theme, "dark");
+    /// ```
+    pub fn map<U: ?Sized, F: Fn(&T) -> &U>(self, f: F) -> IrcRef<U> {
+        let value = NonNull::from(f(self.as_ref()));
+        let owner = ErasedRcBox::new(self.ptr);
+        // The strong reference held by this Irc is transferred to the IrcRef.
+        std::mem::forget(self);
+        IrcRef::new(value, owner)
+    }
 }
 
 impl<T: Clone> Irc<T> {
@@ -117,6 +216,29 @@ impl <T: Default> Default for Irc<T> {
     }
 }
 
+impl<T> From<T> for Irc<T> {
+    fn from(value: T) -> Self {
+        Irc::new(value)
+    }
+}
+
+/// Converts the `Mrc` into an `Irc` pointing to the same value, using `Mrc::into_irc`.
+///
+/// # Example
+/// ```
+/// use yewtil::ptr::{Mrc, Irc};
+/// let mrc: Mrc<usize> = 5.into();
+/// let irc: Irc<usize> = mrc.into();
+///
+/// assert_eq!(*irc, 5);
+/// assert!(irc.is_exclusive());
+/// ```
+impl<T> From<Mrc<T>> for Irc<T> {
+    fn from(mrc: Mrc<T>) -> Self {
+        mrc.into_irc()
+    }
+}
+
 impl<T> Clone for Irc<T> {
     fn clone(&self) -> Self {
         Self {
@@ -151,6 +273,30 @@ impl<T: PartialEq> PartialEq for Irc<T> {
     }
 }
 
+impl<T: PartialEq> PartialEq<T> for Irc<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.as_ref().eq(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<&T> for Irc<T> {
+    fn eq(&self, other: &&T) -> bool {
+        self.as_ref().eq(*other)
+    }
+}
+
+impl PartialEq<str> for Irc<String> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Irc<String> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
 impl<T: Eq> Eq for Irc<T> {}
 
 impl<T: PartialOrd> PartialOrd for Irc<T> {
@@ -173,14 +319,29 @@ impl<T: Hash> Hash for Irc<T> {
 
 impl<T: fmt::Debug> fmt::Debug for Irc<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let rc_box = get_ref_boxed_content(&self.ptr);
         f.debug_struct("Irc")
-            .field("value", rc_box.value.as_ref())
-            .field("count", &rc_box.get_count())
+            .field("value", &self.as_ref())
+            .field("count", &self.get_count())
             .finish()
     }
 }
 
+/// Serializes the wrapped value, without affecting the reference count.
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for Irc<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+/// Deserializes a value into a new, exclusive `Irc`.
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Irc<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Irc::new)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -189,4 +350,83 @@ mod test {
     fn create_new() {
         let _irc = Irc::new(0);
     }
+
+    #[test]
+    fn irc_compares_against_plain_values() {
+        let irc = Irc::new("hello".to_string());
+        assert!(irc == "hello".to_string());
+        assert!(irc == &"hello".to_string());
+        assert!(irc == "hello");
+        assert!(irc != *"world");
+
+        let mrc = Mrc::new(1);
+        assert!(mrc == 1);
+        assert!(mrc != &2);
+    }
+
+    #[test]
+    fn irc_is_a_single_pointer() {
+        assert_eq!(
+            std::mem::size_of::<Irc<String>>(),
+            std::mem::size_of::<usize>()
+        );
+        assert_eq!(
+            std::mem::size_of::<Option<Irc<String>>>(),
+            std::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn exclusive_irc_converts_into_mrc_without_copying() {
+        let mrc = Mrc::new(vec![1]);
+        let weak = mrc.downgrade();
+        let irc = mrc.into_irc();
+        let mut mrc = irc.try_into_mrc().expect("Irc is exclusive");
+        assert_eq!(mrc.get_count(), 1);
+
+        mrc.make_mut().push(2);
+        assert_eq!(*weak.upgrade().expect("Value should not have been copied"), vec![1, 2]);
+    }
+
+    #[test]
+    fn cyclic_irc_is_dropped_with_its_weak_pointer() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Node {
+            this: Weak<Node>,
+            dropped: Rc<Cell<bool>>,
+        }
+        impl Drop for Node {
+            fn drop(&mut self) {
+                self.dropped.set(true)
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(false));
+        let node = Irc::new_cyclic(|this| {
+            assert!(this.upgrade().is_none());
+            Node {
+                this: this.clone(),
+                dropped: dropped.clone(),
+            }
+        });
+        assert!(node.is_exclusive());
+        assert_eq!(node.this.get_weak_count(), 1);
+
+        std::mem::drop(node);
+        assert!(dropped.get());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_irc_is_exclusive() {
+        use serde::de::value::Error as ValueError;
+        use serde::de::IntoDeserializer;
+
+        let deserializer = IntoDeserializer::<ValueError>::into_deserializer(5u32);
+        let irc = Irc::<u32>::deserialize(deserializer).unwrap();
+        assert_eq!(*irc, 5);
+        assert!(irc.is_exclusive());
+    }
 }
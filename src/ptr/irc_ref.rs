@@ -0,0 +1,244 @@
+use crate::ptr::rc_box::ErasedRcBox;
+use crate::ptr::Irc;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::ptr::NonNull;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+/// Immutable Reference Counted pointer to a part of an `Irc`'s value.
+///
+/// An `IrcRef` is created by `Irc::map`, and keeps the whole of the `Irc`'s value alive
+/// while only allowing access to the part of it that was selected.
+/// It shares the reference count of the `Irc` it was created from,
+/// so as long as an `IrcRef` exists, the value can't be unwrapped or mutated in place.
+///
+/// Like `Irc`, cloning an `IrcRef` is cheap, and comparisons are made against the value it points to,
+/// making it suitable for passing to child components that only depend on part of a shared value.
+///
+/// # Example
+/// ```
+/// use yewtil::ptr::{Irc, IrcRef};
+/// let pair = Irc::new((1, "one".to_string()));
+/// let name: IrcRef<str> = pair.clone().map(|pair| pair.1.as_str());
+/// let name_clone = name.clone();
+///
+/// assert_eq!(&*name_clone, "one");
+/// assert_eq!(pair.get_count(), 3);
+/// ```
+pub struct IrcRef<T: ?Sized> {
+    /// Pointer to the part of the value.
+    value: NonNull<T>,
+    /// Strong reference to the allocation that holds the whole value.
+    owner: ErasedRcBox,
+}
+
+impl<T: ?Sized> IrcRef<T> {
+    /// Creates an `IrcRef` from a pointer into the value held by the owner,
+    /// taking over the strong reference represented by the owner.
+    pub(crate) fn new(value: NonNull<T>, owner: ErasedRcBox) -> Self {
+        IrcRef { value, owner }
+    }
+
+    /// Gets the reference count of the `Irc` that this was created from.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::Irc;
+    /// let number = Irc::new((1, 2)).map(|pair| &pair.0);
+    /// assert_eq!(number.get_count(), 1);
+    ///
+    /// let _clone = number.clone();
+    /// assert_eq!(number.get_count(), 2);
+    /// ```
+    pub fn get_count(&self) -> usize {
+        self.owner.get_count()
+    }
+
+    /// Returns true if no other smart pointer refers to the value this points into.
+    pub fn is_exclusive(&self) -> bool {
+        self.get_count() == 1
+    }
+
+    /// Creates an `IrcRef` that points to a part of this `IrcRef`'s value.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::Irc;
+    /// let pairs = Irc::new(((1, 2), (3, 4)));
+    /// let four = pairs.map(|pairs| &pairs.1).map(|pair| &pair.1);
+    /// assert_eq!(*four, 4);
+    /// ```
+    pub fn map<U: ?Sized, F: Fn(&T) -> &U>(self, f: F) -> IrcRef<U> {
+        let value = NonNull::from(f(self.as_ref()));
+        let owner = self.owner;
+        // The strong reference held by this IrcRef is transferred to the new one.
+        std::mem::forget(self);
+        IrcRef { value, owner }
+    }
+}
+
+impl<T: Clone> IrcRef<T> {
+    /// Clones the value that the `IrcRef` points to.
+    pub fn clone_inner(&self) -> T {
+        self.as_ref().clone()
+    }
+}
+
+impl<T: ?Sized> Drop for IrcRef<T> {
+    fn drop(&mut self) {
+        unsafe { self.owner.release() }
+    }
+}
+
+impl<T: ?Sized> Clone for IrcRef<T> {
+    fn clone(&self) -> Self {
+        self.owner.retain();
+        IrcRef {
+            value: self.value,
+            owner: self.owner,
+        }
+    }
+}
+
+/// Converts the `Irc` into an `IrcRef` pointing to the whole of its value.
+impl<T> From<Irc<T>> for IrcRef<T> {
+    fn from(irc: Irc<T>) -> Self {
+        irc.map(|value| value)
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for IrcRef<T> {
+    fn as_ref(&self) -> &T {
+        // The owner keeps the value alive for as long as this IrcRef exists.
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Deref for IrcRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<T: ?Sized> Borrow<T> for IrcRef<T> {
+    fn borrow(&self) -> &T {
+        self.as_ref()
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for IrcRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().eq(other.as_ref())
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq<T> for IrcRef<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.as_ref().eq(other)
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq<&T> for IrcRef<T> {
+    fn eq(&self, other: &&T) -> bool {
+        self.as_ref().eq(*other)
+    }
+}
+
+impl PartialEq<String> for IrcRef<str> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_ref() == other.as_str()
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for IrcRef<T> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for IrcRef<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(other.as_ref())
+    }
+}
+
+impl<T: ?Sized + Ord> Ord for IrcRef<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
+impl<T: ?Sized + Hash> Hash for IrcRef<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for IrcRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IrcRef")
+            .field("value", &self.as_ref())
+            .field("count", &self.get_count())
+            .finish()
+    }
+}
+
+/// Serializes the value pointed to, without affecting the reference count.
+#[cfg(feature = "serde")]
+impl<T: ?Sized + Serialize> Serialize for IrcRef<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn irc_ref_shares_count_with_original() {
+        let irc = Irc::new((1, "one".to_string()));
+        let name = irc.clone().map(|pair| &pair.1);
+        let name_clone = name.clone().map(|name| name.as_str());
+        assert_eq!(irc.get_count(), 3);
+
+        std::mem::drop(name);
+        assert_eq!(irc.get_count(), 2);
+        assert_eq!(&*name_clone, "one");
+
+        std::mem::drop(name_clone);
+        assert!(irc.is_exclusive());
+        assert!(irc.try_unwrap().is_ok());
+    }
+
+    #[test]
+    fn irc_ref_to_str_compares_like_its_contents() {
+        let name = Irc::new("hello".to_string()).map(|name| name.as_str());
+        assert!(name == *"hello");
+        assert!(name == "hello");
+        assert!(name == "hello".to_string());
+    }
+
+    #[test]
+    fn irc_ref_keeps_the_value_alive() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Flagged(Rc<Cell<bool>>, u32);
+        impl Drop for Flagged {
+            fn drop(&mut self) {
+                self.0.set(true)
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(false));
+        let number = Irc::new(Flagged(dropped.clone(), 5)).map(|flagged| &flagged.1);
+        assert!(!dropped.get());
+        assert_eq!(*number, 5);
+
+        std::mem::drop(number);
+        assert!(dropped.get());
+    }
+}
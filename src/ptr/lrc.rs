@@ -393,6 +393,91 @@ impl<T> Lrc<T> {
         }
     }
 
+    /// Calls the function with a reference to the value of the next (older) node,
+    /// without moving this `Lrc`'s head.
+    ///
+    /// Returns `None` if there is no older node.
+    ///
+    /// # Note
+    /// The neighboring node is owned by other `Lrc`s, which could be dropped while a reference to
+    /// its value is held, so the value is only lent to the function rather than returned.
+    /// The node is kept alive for the duration of the call, after which the reference counts are
+    /// as they were.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let mut lrc = Lrc::new(0);
+    /// let _clone = lrc.clone();
+    /// lrc.set(1);
+    ///
+    /// assert_eq!(lrc.peek_next(|value| *value), Some(0));
+    /// assert_eq!(lrc.as_ref(), &1);
+    /// assert_eq!(lrc.peek_back(|value| *value), None);
+    /// ```
+    pub fn peek_next<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.get_ref_head_node()
+            .next
+            .map(|ptr| f(Self::from_shared_node(ptr).as_ref()))
+    }
+
+    /// Calls the function with a reference to the value of the previous (newer) node,
+    /// without moving this `Lrc`'s head.
+    ///
+    /// Returns `None` if there is no newer node.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let mut lrc = Lrc::new(0);
+    /// let mut clone = lrc.clone();
+    /// clone.set(1);
+    ///
+    /// assert_eq!(lrc.peek_back(|value| *value), Some(1));
+    /// assert_eq!(lrc.as_ref(), &0);
+    /// ```
+    pub fn peek_back<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.get_ref_head_node()
+            .prev
+            .map(|ptr| f(Self::from_shared_node(ptr).as_ref()))
+    }
+
+    /// Calls the function with the value of the head and each of its older nodes,
+    /// ordered from newest to oldest, collecting the results.
+    ///
+    /// Like `peek_next`, the values are only lent to the function, because the older nodes are owned by
+    /// other `Lrc`s. Each node is kept alive while the function is called with its value,
+    /// and the reference counts are as they were once this returns.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let mut lrc = Lrc::new(0);
+    /// let _first = lrc.clone();
+    /// lrc.set(1);
+    /// let _second = lrc.clone();
+    /// lrc.set(2);
+    ///
+    /// assert_eq!(lrc.map_values(|value| value * 10), vec![20, 10, 0]);
+    /// ```
+    pub fn map_values<R>(&self, mut f: impl FnMut(&T) -> R) -> Vec<R> {
+        let mut values = Vec::new();
+        let mut node = Some(self.clone());
+        while let Some(lrc) = node {
+            values.push(f(lrc.as_ref()));
+            node = lrc.get_ref_head_node().next.map(Lrc::from_shared_node);
+        }
+        values
+    }
+
+    /// Creates a new `Lrc` whose head is a node that is already held by other `Lrc`s.
+    fn from_shared_node(ptr: NonNull<Node<T>>) -> Self {
+        unsafe {
+            ptr.as_ref().inc_count();
+        }
+        Lrc { head: Some(ptr) }
+    }
+
     /// Compares head pointers for equality.
     ///
     /// # Example
@@ -474,6 +559,9 @@ impl<T> Lrc<T> {
     /// It is a `O(n)` operation, dependent on how many nodes are connected to this `Lrc`'s head.
     /// Or put another way, how many `Lrc`s exist in the program that were cloned from this `Lrc`
     /// (or this `Lrc` was cloned from one of them), that have differing head nodes.
+    ///
+    /// Because a node is deallocated as soon as no `Lrc` has it as its head,
+    /// the list can be shortened by dropping the `Lrc`s that hold its oldest nodes.
     pub fn len(&self) -> usize {
         // This node, plus the length of its next nodes and its prev nodes
         1 + self.next_len() + self.prev_len()
@@ -572,13 +660,76 @@ impl<T: Clone> Lrc<T> {
         }
     }
 
+    /// Consumes this Lrc, returning the value of its head and each of its older nodes,
+    /// ordered from newest to oldest.
+    ///
+    /// Like `clone_unwrap`, the head's value is moved out if this Lrc has exclusive access to it.
+    /// The older nodes are always held by other `Lrc`s, so their values are cloned.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let mut lrc = Lrc::new(0);
+    /// let _first = lrc.clone();
+    /// lrc.set(1);
+    ///
+    /// assert_eq!(lrc.into_values(), vec![1, 0]);
+    /// ```
+    pub fn into_values(self) -> Vec<T> {
+        let older = match self.get_ref_head_node().next {
+            Some(next) => Lrc::from_shared_node(next).map_values(T::clone),
+            None => Vec::new(),
+        };
+        let mut values = Vec::with_capacity(older.len() + 1);
+        values.push(self.clone_unwrap());
+        values.extend(older);
+        values
+    }
+
     /// Clones the wrapped value at the `Lrc`'s head.
+    ///
+    /// This takes an owned snapshot of the current value, leaving the linked list untouched.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let mut lrc = Lrc::new(vec![1]);
+    /// let snapshot: Vec<i32> = lrc.clone_inner();
+    ///
+    /// lrc.make_mut().push(2);
+    /// assert_eq!(snapshot, vec![1]);
+    /// assert_eq!(lrc.len(), 1);
+    /// ```
     pub fn clone_inner(&self) -> T {
         self.get_ref_head_node().value.as_ref().clone()
     }
 }
 
 impl<T: PartialEq> Lrc<T> {
+    /// Compares head pointers, only comparing the values if the pointers differ.
+    ///
+    /// Because `set` and `make_mut` move an `Lrc` to a new head when they change its value,
+    /// `Lrc`s that share a head are equal, which makes this an O(1) check in the common case.
+    /// This is useful for implementing `change` for components whose props hold large values in `Lrc`s.
+    ///
+    /// # Note
+    /// Unlike `==`, this will consider the values to be equal if they share a head,
+    /// even if `T`'s `PartialEq` isn't reflexive (eg. `f64::NAN`).
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Lrc;
+    /// let lrc1 = Lrc::new("Some String".to_string());
+    /// let lrc2 = lrc1.clone();
+    /// let lrc3 = Lrc::new("Some String".to_string());
+    ///
+    /// assert!(Lrc::ptr_eq_or_value_eq(&lrc1, &lrc2), "Shares a head, so the values aren't compared");
+    /// assert!(Lrc::ptr_eq_or_value_eq(&lrc1, &lrc3), "Values are compared because the heads differ");
+    /// ```
+    pub fn ptr_eq_or_value_eq(lhs: &Self, rhs: &Self) -> bool {
+        Self::ptr_eq(lhs, rhs) || lhs == rhs
+    }
+
     /// Only sets if the new value is different than the current value.
     ///
     /// It will return true if they were not equal, indicating that an assignment has occurred.
@@ -680,23 +831,13 @@ impl<T> Iterator for Lrc<T> {
     type Item = Lrc<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.get_ref_head_node().next.map(|ptr| {
-            unsafe {
-                ptr.as_ref().inc_count();
-            }
-            Lrc { head: Some(ptr) }
-        })
+        self.get_ref_head_node().next.map(Lrc::from_shared_node)
     }
 }
 
 impl<T> DoubleEndedIterator for Lrc<T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.get_ref_head_node().prev.map(|ptr| {
-            unsafe {
-                ptr.as_ref().inc_count();
-            }
-            Lrc { head: Some(ptr) }
-        })
+        self.get_ref_head_node().prev.map(Lrc::from_shared_node)
     }
 }
 
@@ -975,6 +1116,21 @@ mod test {
         );
     }
 
+    #[test]
+    fn peek_keeps_node_alive_if_its_owner_is_dropped() {
+        let mut lrc = Lrc::new(vec![0]);
+        let clone = lrc.clone();
+        lrc.set(vec![1]);
+
+        let peeked = lrc.peek_next(move |value| {
+            std::mem::drop(clone);
+            value.clone()
+        });
+        assert_eq!(peeked, Some(vec![0]));
+        assert!(!lrc.has_next());
+        assert_eq!(lrc.get_count(), 1);
+    }
+
     #[test]
     fn advance_next() {
         let mut lrc = Lrc::new(0);
@@ -4,17 +4,25 @@
 //! or functionality not available in `Rc`.
 #[cfg(feature = "mrc_irc")]
 mod irc;
+#[cfg(feature = "mrc_irc")]
+mod irc_ref;
 #[cfg(feature = "lrc")]
 mod lrc;
 #[cfg(feature = "mrc_irc")]
 mod mrc;
 mod rc_box;
 mod takeable;
+#[cfg(feature = "mrc_irc")]
+mod weak;
 
 #[cfg(feature = "mrc_irc")]
 pub use irc::Irc;
 #[cfg(feature = "mrc_irc")]
+pub use irc_ref::IrcRef;
+#[cfg(feature = "mrc_irc")]
 pub use mrc::Mrc;
+#[cfg(feature = "mrc_irc")]
+pub use weak::Weak;
 #[cfg(feature = "lrc")]
 pub use lrc::Lrc;
 
@@ -2,7 +2,7 @@ use crate::ptr::rc_box::{
     clone_impl, clone_inner, decrement_and_possibly_deallocate, get_count, get_mut_boxed_content,
     get_ref_boxed_content, is_exclusive, try_unwrap, unwrap_clone, RcBox,
 };
-use crate::ptr::Irc;
+use crate::ptr::{Irc, Weak};
 use std::borrow::{Borrow, BorrowMut};
 use std::cmp::Ordering;
 use std::fmt;
@@ -10,12 +10,13 @@ use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Mutable Reference Counted pointer
 ///
 /// The `Mrc` has similar semantics to `std::rc::Rc` pointer,
-/// with notable differences that it does not support `Weak` pointers,
-/// it supports `std::ops::DerefMut` via the possibly allocating `make_mut` function,
+/// with notable differences that it supports `std::ops::DerefMut` via the possibly allocating `make_mut` function,
 /// and that it can create immutable handles to its data (`Irc`).
 ///
 /// This should make it just slightly more size efficient and performant than `Rc`,
@@ -50,7 +51,7 @@ use std::ptr::NonNull;
 /// ```
 pub struct Mrc<T> {
     /// Pointer to the value and reference counter.
-    ptr: NonNull<RcBox<T>>,
+    pub(crate) ptr: NonNull<RcBox<T>>,
 }
 
 impl<T> Mrc<T> {
@@ -77,7 +78,9 @@ impl<T> Mrc<T> {
     /// ```
     pub fn get_mut(&mut self) -> Option<&mut T> {
         if self.is_exclusive() {
-            Some(get_mut_boxed_content(&mut self.ptr).value.as_mut())
+            let content = get_mut_boxed_content(&mut self.ptr);
+            content.inc_generation();
+            Some(content.value.as_mut())
         } else {
             None
         }
@@ -141,6 +144,31 @@ impl<T> Mrc<T> {
         is_exclusive(self.ptr)
     }
 
+    /// Gets the number of times a mutable reference to the value has been handed out,
+    /// through `make_mut`, `get_mut`, or their `DerefMut`, `AsMut` and `BorrowMut` counterparts.
+    ///
+    /// This allows coarse change detection without requiring `PartialEq`:
+    /// if the generation hasn't changed since it was last checked, the value hasn't been modified.
+    /// A copy made by `make_mut` continues counting from the value it was copied from.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::Mrc;
+    /// let mut mrc = Mrc::new(0);
+    /// assert_eq!(mrc.generation(), 0);
+    ///
+    /// *mrc += 1;
+    /// assert_eq!(mrc.generation(), 1);
+    ///
+    /// let clone = mrc.clone();
+    /// *mrc += 1;
+    /// assert_eq!(mrc.generation(), 2);
+    /// assert_eq!(clone.generation(), 1);
+    /// ```
+    pub fn generation(&self) -> u64 {
+        get_ref_boxed_content(&self.ptr).get_generation()
+    }
+
     /// Returns an immutable reference counted pointer,
     /// pointing to the same value and reference count.
     ///
@@ -157,6 +185,22 @@ impl<T> Mrc<T> {
         Irc { ptr: self.ptr }
     }
 
+    /// Creates a `Weak` pointer to the value, which doesn't keep the value alive.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::{Mrc, Weak};
+    /// let mrc: Mrc<usize> = Mrc::new(0);
+    /// let weak: Weak<usize> = mrc.downgrade();
+    ///
+    /// assert!(mrc.is_exclusive());
+    /// assert!(weak.upgrade().is_some());
+    /// ```
+    pub fn downgrade(&self) -> Weak<T> {
+        get_ref_boxed_content(&self.ptr).inc_weak_count();
+        Weak { ptr: self.ptr }
+    }
+
     /// Converts this Mrc into an Irc.
     /// # Example
     /// ```
@@ -173,6 +217,51 @@ impl<T> Mrc<T> {
         Irc { ptr: self.ptr }
     }
 
+    /// Returns a mutable reference to the value if it has exclusive access.
+    /// If it does not have exclusive access, it will call the function to create a new value to own,
+    /// leaving the shared value alone.
+    ///
+    /// This is like `make_mut`, but doesn't require the value to be `Clone`.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Mrc;
+    /// let mut mrc: Mrc<Vec<usize>> = Mrc::new(vec![1]);
+    ///
+    /// mrc.make_mut_or(Vec::new).push(2);
+    /// assert_eq!(*mrc, vec![1, 2]);
+    ///
+    /// let clone = mrc.clone();
+    /// mrc.make_mut_or(Vec::new).push(3);
+    /// assert_eq!(*mrc, vec![3]);
+    /// assert_eq!(*clone, vec![1, 2]);
+    /// ```
+    pub fn make_mut_or<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if !self.is_exclusive() {
+            self.detach(f());
+        }
+
+        let content = get_mut_boxed_content(&mut self.ptr);
+        content.inc_generation();
+        content.value.as_mut()
+    }
+
+    /// Points this `Mrc` at a newly allocated value, away from the shared value it pointed to.
+    fn detach(&mut self, value: T) {
+        let rc_box = RcBox::new(value);
+        rc_box.set_generation(self.generation());
+        let ptr = rc_box.into_non_null();
+
+        // decrement the count for the boxed content at the current pointer
+        // because this Mrc will point to a new value.
+
+        // This doesn't need to check to deallocate, because the count is guaranteed to be > 1.
+        get_ref_boxed_content(&self.ptr).dec_count();
+
+        // Replace the pointers
+        self.ptr = ptr;
+    }
+
     /// Checks pointers for equality.
     ///
     /// # Example
@@ -209,20 +298,12 @@ impl<T: Clone> Mrc<T> {
     /// ```
     pub fn make_mut(&mut self) -> &mut T {
         if !self.is_exclusive() {
-            let rc_box = RcBox::new(self.clone_inner());
-            let ptr = rc_box.into_non_null();
-
-            // decrement the count for the boxed content at the current pointer
-            // because this Mrc will point to a new value.
-
-            // This doesn't need to check to deallocate, because the count is guaranteed to be > 1.
-            get_ref_boxed_content(&self.ptr).dec_count();
-
-            // Replace the pointers
-            self.ptr = ptr;
+            self.detach(self.clone_inner());
         }
 
-        get_mut_boxed_content(&mut self.ptr).value.as_mut()
+        let content = get_mut_boxed_content(&mut self.ptr);
+        content.inc_generation();
+        content.value.as_mut()
     }
 
     /// Consumes the `Mrc` and returns the value from the `Mrc` if it is not shared
@@ -236,6 +317,45 @@ impl<T: Clone> Mrc<T> {
     }
 }
 
+impl<T: Clone + PartialEq> Mrc<T> {
+    /// Mutates the value through `make_mut` and returns whether it actually changed.
+    ///
+    /// If the value was shared and `f` left it unchanged, the `Mrc` goes back to pointing at the
+    /// shared value instead of holding onto the copy.
+    ///
+    /// # Example
+    /// ```
+    ///# use yewtil::ptr::Mrc;
+    /// let mut mrc: Mrc<usize> = Mrc::new(0);
+    /// let clone = mrc.clone();
+    ///
+    /// assert!(!mrc.modify(|x| *x = 0));
+    /// assert!(Mrc::ptr_eq(&mrc, &clone));
+    ///
+    /// assert!(mrc.modify(|x| *x += 1));
+    /// assert_eq!(*mrc, 1);
+    /// assert_eq!(*clone, 0);
+    /// ```
+    pub fn modify(&mut self, f: impl FnOnce(&mut T)) -> bool {
+        if self.is_exclusive() {
+            let snapshot = self.clone_inner();
+            let value = self.make_mut();
+            f(value);
+            *value != snapshot
+        } else {
+            // make_mut is about to copy the value, so the shared original serves as the snapshot.
+            let original = self.clone();
+            f(self.make_mut());
+            if self.as_ref() == original.as_ref() {
+                *self = original;
+                false
+            } else {
+                true
+            }
+        }
+    }
+}
+
 impl<T> Drop for Mrc<T> {
     fn drop(&mut self) {
         unsafe { decrement_and_possibly_deallocate(self.ptr) }
@@ -257,6 +377,12 @@ impl <T: Default> Default for Mrc<T> {
     }
 }
 
+impl<T> From<T> for Mrc<T> {
+    fn from(value: T) -> Self {
+        Mrc::new(value)
+    }
+}
+
 impl<T: Clone> DerefMut for Mrc<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.make_mut()
@@ -301,6 +427,30 @@ impl<T: PartialEq> PartialEq for Mrc<T> {
     }
 }
 
+impl<T: PartialEq> PartialEq<T> for Mrc<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.as_ref().eq(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<&T> for Mrc<T> {
+    fn eq(&self, other: &&T) -> bool {
+        self.as_ref().eq(*other)
+    }
+}
+
+impl PartialEq<str> for Mrc<String> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Mrc<String> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
 impl<T: Eq> Eq for Mrc<T> {}
 
 impl<T: PartialOrd> PartialOrd for Mrc<T> {
@@ -330,3 +480,19 @@ impl<T: fmt::Debug> fmt::Debug for Mrc<T> {
             .finish()
     }
 }
+
+/// Serializes the wrapped value, without affecting the reference count.
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for Mrc<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        get_ref_boxed_content(&self.ptr).value.as_ref().serialize(serializer)
+    }
+}
+
+/// Deserializes a value into a new, exclusive `Mrc`.
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Mrc<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Mrc::new)
+    }
+}
@@ -1,12 +1,15 @@
 use crate::ptr::takeable::Takeable;
 use crate::ptr::IsZero;
 use std::cell::Cell;
+use std::marker::PhantomData;
 use std::ptr::NonNull;
 
 #[derive(Debug)]
 pub(crate) struct RcBox<T> {
     pub(crate) value: Takeable<T>,
     count: Cell<usize>,
+    weak_count: Cell<usize>,
+    generation: Cell<u64>,
 }
 
 /// The boxed content used in Irc and Mrc.
@@ -16,9 +19,29 @@ impl<T> RcBox<T> {
         Self {
             value: Takeable::new(value),
             count: Cell::new(1),
+            weak_count: Cell::new(0),
+            generation: Cell::new(0),
         }
     }
 
+    /// Creates a node without a value, and without any strong references to it.
+    ///
+    /// Weak references may be made to the node, but they can't be upgraded until `init` has been called.
+    pub(crate) fn uninit() -> Self {
+        Self {
+            value: Takeable::empty(),
+            count: Cell::new(0),
+            weak_count: Cell::new(0),
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Places the value of a node created by `uninit`, creating the first strong reference to it.
+    pub(crate) fn init(&mut self, value: T) {
+        self.value.put(value);
+        self.inc_count();
+    }
+
     #[inline]
     pub(crate) fn into_non_null(self) -> NonNull<Self> {
         unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(self))) }
@@ -48,20 +71,71 @@ impl<T> RcBox<T> {
         count == 0
     }
 
+    /// Gets the number of weak pointers to the node.
+    pub(crate) fn get_weak_count(&self) -> usize {
+        self.weak_count.get()
+    }
+
+    /// Increments the weak reference count of the node.
+    #[inline]
+    pub(crate) fn inc_weak_count(&self) {
+        self.weak_count.set(self.weak_count.get() + 1);
+    }
+
+    /// Decrements the weak reference count of the node.
+    /// It will return true if the node can be deallocated,
+    /// which is when both the weak and strong counts are zero.
+    #[inline]
+    pub(crate) fn dec_weak_count(&self) -> IsZero {
+        let weak_count = self.weak_count.get() - 1;
+        self.weak_count.set(weak_count);
+        weak_count == 0 && self.get_count() == 0
+    }
+
     #[inline]
     pub(crate) fn is_exclusive(&self) -> bool {
         self.get_count() == 1
     }
+
+    /// Gets the number of times a mutable reference to the value has been handed out.
+    pub(crate) fn get_generation(&self) -> u64 {
+        self.generation.get()
+    }
+
+    /// Sets the generation, so a copy of a value can continue counting from the original.
+    pub(crate) fn set_generation(&self, generation: u64) {
+        self.generation.set(generation);
+    }
+
+    /// Records that a mutable reference to the value is being handed out.
+    #[inline]
+    pub(crate) fn inc_generation(&self) {
+        self.generation.set(self.generation.get() + 1);
+    }
 }
 
 #[inline]
-pub(crate) unsafe fn decrement_and_possibly_deallocate<T>(node: NonNull<RcBox<T>>) {
+pub(crate) unsafe fn decrement_and_possibly_deallocate<T>(mut node: NonNull<RcBox<T>>) {
     // If the ref-count becomes 0
     if node.as_ref().dec_count() {
-        std::ptr::drop_in_place(node.as_ptr());
+        // The value is dropped as soon as no strong pointers remain,
+        // but the allocation has to outlive any weak pointers.
+        // The value may itself hold weak pointers to the node (eg. if made by `Irc::new_cyclic`),
+        // so a weak reference is held while it is dropped to keep them from deallocating the node.
+        node.as_ref().inc_weak_count();
+        node.as_mut().value.clear();
+        if node.as_ref().dec_weak_count() {
+            deallocate(node);
+        }
     }
 }
 
+/// Frees the node's allocation.
+#[inline]
+pub(crate) unsafe fn deallocate<T>(node: NonNull<RcBox<T>>) {
+    drop(Box::from_raw(node.as_ptr()));
+}
+
 #[inline(always)]
 pub(crate) fn get_mut_boxed_content<T>(ptr: &mut NonNull<RcBox<T>>) -> &mut RcBox<T> {
     unsafe { ptr.as_mut() }
@@ -115,3 +189,69 @@ pub(crate) fn clone_impl<T>(ptr: NonNull<RcBox<T>>) -> NonNull<RcBox<T>> {
     // rerturn the ptr
     ptr
 }
+
+/// A strong reference to an `RcBox` whose value's type has been erased.
+///
+/// This is used to keep an allocation alive while only a part of its value is referenced.
+/// Copying it doesn't affect the reference count, so `retain` and `release` must be called
+/// to balance the count whenever copies are created and dropped.
+#[derive(Clone, Copy)]
+pub(crate) struct ErasedRcBox {
+    ptr: NonNull<()>,
+    vtable: &'static RcBoxVTable,
+}
+
+/// The operations on an `RcBox<T>` that are needed once `T` has been erased.
+struct RcBoxVTable {
+    count: fn(NonNull<()>) -> usize,
+    retain: fn(NonNull<()>),
+    release: unsafe fn(NonNull<()>),
+}
+
+/// Holds the vtable for `RcBox<T>`, so that a single static copy exists for each `T`.
+struct VTableFor<T>(PhantomData<T>);
+
+impl<T> VTableFor<T> {
+    const VTABLE: RcBoxVTable = RcBoxVTable {
+        count: count_erased::<T>,
+        retain: retain_erased::<T>,
+        release: release_erased::<T>,
+    };
+}
+
+impl ErasedRcBox {
+    /// Erases the type of the pointer, taking over the strong reference it represents.
+    pub(crate) fn new<T>(ptr: NonNull<RcBox<T>>) -> Self {
+        ErasedRcBox {
+            ptr: ptr.cast(),
+            vtable: &VTableFor::<T>::VTABLE,
+        }
+    }
+
+    /// Gets the reference count of the allocation.
+    pub(crate) fn get_count(self) -> usize {
+        (self.vtable.count)(self.ptr)
+    }
+
+    /// Increments the reference count of the allocation.
+    pub(crate) fn retain(self) {
+        (self.vtable.retain)(self.ptr)
+    }
+
+    /// Decrements the reference count of the allocation, deallocating it if the count hits zero.
+    pub(crate) unsafe fn release(self) {
+        (self.vtable.release)(self.ptr)
+    }
+}
+
+fn count_erased<T>(ptr: NonNull<()>) -> usize {
+    get_count(ptr.cast::<RcBox<T>>())
+}
+
+fn retain_erased<T>(ptr: NonNull<()>) {
+    get_ref_boxed_content(&ptr.cast::<RcBox<T>>()).inc_count()
+}
+
+unsafe fn release_erased<T>(ptr: NonNull<()>) {
+    decrement_and_possibly_deallocate(ptr.cast::<RcBox<T>>())
+}
@@ -6,6 +6,13 @@ use std::fmt;
 /// it will never be accessed after items have been taken.
 ///
 /// Accessing this via `as_ref` or `as_mut` after taking the value will cause a panic.
+///
+/// # Note
+/// This intentionally isn't backed by `MaybeUninit<T>`.
+/// The pointers that own a `Takeable` may clear it after its value was taken (eg. when an `Mrc` is
+/// dropped after `try_unwrap`), so whether the value is present has to be tracked in every build,
+/// which would take up as much space as `Option`'s discriminant.
+/// `Option<T>` also takes no extra space at all when `T` has a niche, such as references or `Box`es.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Takeable<T>(Option<T>);
 
@@ -14,10 +21,26 @@ impl<T> Takeable<T> {
         Takeable(Some(item))
     }
 
+    /// Creates a `Takeable` that doesn't hold a value yet.
+    pub(crate) fn empty() -> Self {
+        Takeable(None)
+    }
+
+    /// Places the value, which must not have been placed already.
+    pub(crate) fn put(&mut self, item: T) {
+        debug_assert!(self.0.is_none(), "Can't put a value twice");
+        self.0 = Some(item);
+    }
+
     /// This should only be called once.
     pub(crate) fn take(&mut self) -> T {
         self.0.take().expect("Can't take twice")
     }
+
+    /// Drops the value if it hasn't already been taken.
+    pub(crate) fn clear(&mut self) {
+        self.0 = None;
+    }
 }
 
 impl<T> AsRef<T> for Takeable<T> {
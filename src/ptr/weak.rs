@@ -0,0 +1,148 @@
+use crate::ptr::rc_box::{deallocate, get_ref_boxed_content, RcBox};
+use crate::ptr::Mrc;
+use std::fmt;
+use std::ptr::NonNull;
+
+/// A non-owning pointer to the value of an `Mrc`.
+///
+/// A `Weak` doesn't keep the value alive, so it can be used for back-references
+/// (eg. from a child node to its parent) without creating reference cycles that would leak memory.
+/// To access the value, it must be upgraded to an `Mrc`, which will fail if the value has been dropped.
+///
+/// # Example
+/// ```
+/// use yewtil::ptr::Mrc;
+/// let mrc = Mrc::new(5);
+/// let weak = mrc.downgrade();
+/// assert_eq!(weak.upgrade().map(|mrc| *mrc), Some(5));
+///
+/// std::mem::drop(mrc);
+/// assert!(weak.upgrade().is_none());
+/// ```
+pub struct Weak<T> {
+    /// Pointer to the value and reference counters.
+    pub(crate) ptr: NonNull<RcBox<T>>,
+}
+
+impl<T> Weak<T> {
+    /// Attempts to get an `Mrc` pointing to the value,
+    /// returning `None` if the value has already been dropped.
+    pub fn upgrade(&self) -> Option<Mrc<T>> {
+        let rc_box = get_ref_boxed_content(&self.ptr);
+        if rc_box.get_count() == 0 {
+            None
+        } else {
+            rc_box.inc_count();
+            Some(Mrc { ptr: self.ptr })
+        }
+    }
+
+    /// Gets the number of `Mrc`s and `Irc`s pointing to the value.
+    ///
+    /// If this is `0`, then the value has been dropped.
+    pub fn get_count(&self) -> usize {
+        get_ref_boxed_content(&self.ptr).get_count()
+    }
+
+    /// Gets the number of `Weak` pointers pointing to the value.
+    pub fn get_weak_count(&self) -> usize {
+        get_ref_boxed_content(&self.ptr).get_weak_count()
+    }
+
+    /// Gets the generation of the value, as returned by `Mrc::generation`.
+    ///
+    /// Because a `Weak` doesn't stop an `Mrc` from mutating the value in place,
+    /// this can be used to find out that the value has changed without upgrading the `Weak`.
+    ///
+    /// # Example
+    /// ```
+    /// use yewtil::ptr::Mrc;
+    /// let mut mrc = Mrc::new(0);
+    /// let weak = mrc.downgrade();
+    ///
+    /// *mrc += 1;
+    /// assert_eq!(weak.generation(), 1);
+    /// ```
+    pub fn generation(&self) -> u64 {
+        get_ref_boxed_content(&self.ptr).get_generation()
+    }
+}
+
+impl<T> Clone for Weak<T> {
+    fn clone(&self) -> Self {
+        get_ref_boxed_content(&self.ptr).inc_weak_count();
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for Weak<T> {
+    fn drop(&mut self) {
+        if get_ref_boxed_content(&self.ptr).dec_weak_count() {
+            unsafe { deallocate(self.ptr) }
+        }
+    }
+}
+
+/// `Weak` pointers are equal if they point to the same value.
+///
+/// The values themselves aren't compared, as they may have been dropped.
+impl<T> PartialEq for Weak<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for Weak<T> {}
+
+impl<T> fmt::Debug for Weak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Records when it is dropped.
+    struct DropFlag(Rc<Cell<bool>>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true)
+        }
+    }
+
+    #[test]
+    fn value_is_dropped_while_weak_pointer_remains() {
+        let dropped = Rc::new(Cell::new(false));
+        let mrc = Mrc::new(DropFlag(dropped.clone()));
+        let weak = mrc.downgrade();
+        let weak_clone = weak.clone();
+        assert_eq!(weak.get_weak_count(), 2);
+
+        std::mem::drop(mrc);
+        assert!(dropped.get());
+        assert_eq!(weak.get_count(), 0);
+        assert!(weak_clone.upgrade().is_none());
+    }
+
+    #[test]
+    fn upgrade_increments_count() {
+        let mrc = Mrc::new(0);
+        let weak = mrc.downgrade();
+        let upgraded = weak.upgrade().expect("value should be alive");
+        assert_eq!(mrc.get_count(), 2);
+        assert!(Mrc::ptr_eq(&mrc, &upgraded));
+    }
+
+    #[test]
+    fn weak_pointers_to_the_same_value_are_equal() {
+        let mut mrc = Mrc::new(0);
+        let weak = mrc.downgrade();
+        *mrc += 1;
+        assert_eq!(weak, mrc.downgrade());
+        assert_ne!(weak, Mrc::new(1).downgrade());
+    }
+}
@@ -6,6 +6,24 @@ use yew::{Component, ComponentLink, Html, Properties, ShouldRender};
 pub trait PureComponent: Properties +  PartialEq + Sized + 'static {
     /// Renders self to `Html`.
     fn render(&self) -> Html;
+
+    /// Called after the component has been mounted to the DOM.
+    ///
+    /// Because pure components are immutable, this can only be used for side effects,
+    /// like focusing an element or invoking a callback prop.
+    /// Returning `true` will cause the component to re-render.
+    fn mounted(&self) -> ShouldRender {
+        false
+    }
+
+    /// Gets the generation of state that is rendered by the component, but not compared as part of its props,
+    /// such as the `generation` of a `Weak` pointer to an `Mrc`.
+    ///
+    /// If the generation differs from the one that was last rendered, the component will re-render,
+    /// even when its props are equal.
+    fn generation(&self) -> u64 {
+        0
+    }
 }
 
 
@@ -39,15 +57,115 @@ pub trait PureComponent: Properties +  PartialEq + Sized + 'static {
 /// /// Use this from within `html!` macros.
 /// pub type MyComponent = Pure<PureMyComponent>;
 /// ```
+///
+/// The `PureComponent` implementation and the alias can instead be generated using `#[derive(Pure)]`,
+/// which calls a `view` method declared on the struct itself.
+/// A different method can be named using `#[pure(render = "draw")]`:
+///
+/// ```
+/// use yew::Properties;
+/// use yew::Html;
+/// use yewtil::Pure;
+///
+/// #[derive(Properties, PartialEq, Pure)]
+/// pub struct PureMyComponent {
+///     pub data: String
+/// }
+///
+/// impl PureMyComponent {
+///     fn view(&self) -> Html {
+///#        unimplemented!()
+///         // ...
+///     }
+/// }
+///
+/// // `MyComponent` is now an alias for `Pure<PureMyComponent>`.
+///# let _: Option<MyComponent> = None;
+/// ```
+///
+/// # Children
+/// Pure components can wrap arbitrary child content, which makes them a good fit for layout
+/// primitives like cards and modals.
+/// Declare a `children: Children` field on the props and `Pure` will fill it with the content
+/// nested inside the component in `html!`, ready to be rendered:
+///
+/// ```
+/// use yew::{html, Children, Html, Properties};
+/// use yewtil::{Pure, PureComponent};
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct PureCard {
+///     pub children: Children,
+/// }
+///
+/// impl PureComponent for PureCard {
+///     fn render(&self) -> Html {
+///         html! {
+///             <div class="card">
+///                 { self.children.iter().collect::<Html>() }
+///             </div>
+///         }
+///     }
+/// }
+///
+/// /// Used as `<Card>{ "Content" }</Card>`.
+/// pub type Card = Pure<PureCard>;
+/// ```
+///
+/// # Rendering
+/// `Pure` only re-renders when its new props aren't equal to its current ones,
+/// when their `PureComponent::generation` differs from the last rendered one,
+/// or when `PureComponent::mounted` returns `true`,
+/// so `render` isn't called again for a parent's re-render that passes identical props.
+///
+/// The generation allows a component to depend on a value that is mutated in place elsewhere.
+/// In the following example, the counter's `Weak` pointer stays the same when the parent increments its `Mrc`,
+/// so the props are equal, but the counter still re-renders because the `Mrc`'s generation has advanced.
+///
+/// ```
+/// use yew::{html, Html, Properties};
+/// use yewtil::ptr::Weak;
+/// use yewtil::{Pure, PureComponent};
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct PureCounter {
+///     #[props(required)]
+///     pub count: Weak<u32>,
+/// }
+///
+/// impl PureComponent for PureCounter {
+///     fn render(&self) -> Html {
+///         let count = self.count.upgrade().map(|count| *count).unwrap_or_default();
+///         html! { <p>{ count }</p> }
+///     }
+///
+///     fn generation(&self) -> u64 {
+///         self.count.generation()
+///     }
+/// }
+///
+/// pub type Counter = Pure<PureCounter>;
+/// ```
 #[derive(Debug)]
-pub struct Pure<T>(T);
+pub struct Pure<T> {
+    props: T,
+    /// The generation of the props when they were last rendered.
+    generation: u64,
+}
 
 impl<T: PureComponent + 'static> Component for Pure<T> {
     type Message = ();
     type Properties = T;
 
     fn create(props: Self::Properties, _link: ComponentLink<Self>) -> Self {
-        Pure(props)
+        Pure {
+            generation: props.generation(),
+            props,
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        self.props.mounted()
     }
 
     fn update(&mut self, _msg: Self::Message) -> ShouldRender {
@@ -55,10 +173,68 @@ impl<T: PureComponent + 'static> Component for Pure<T> {
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
-        self.0.neq_assign(props)
+        let generation = props.generation();
+        let advanced = generation != self.generation;
+        self.generation = generation;
+        self.props.neq_assign(props) || advanced
     }
 
     fn view(&self) -> Html {
-        self.0.render()
+        self.props.render()
+    }
+}
+
+#[cfg(all(test, feature = "mrc_irc"))]
+mod test {
+    use super::*;
+    use crate::ptr::{Mrc, Weak};
+    use yew::html;
+
+    #[derive(Properties, PartialEq)]
+    struct PureCounter {
+        #[props(required)]
+        count: Weak<u32>,
+    }
+
+    impl PureComponent for PureCounter {
+        fn render(&self) -> Html {
+            html! {}
+        }
+
+        fn generation(&self) -> u64 {
+            self.count.generation()
+        }
+    }
+
+    fn pure<T: PureComponent>(props: T) -> Pure<T> {
+        Pure {
+            generation: props.generation(),
+            props,
+        }
+    }
+
+    #[test]
+    fn generation_advancing_triggers_rerender_for_equal_props() {
+        let mut count = Mrc::new(0);
+        let mut counter = pure(PureCounter {
+            count: count.downgrade(),
+        });
+
+        let unchanged = PureCounter {
+            count: count.downgrade(),
+        };
+        assert!(!counter.change(unchanged));
+
+        *count += 1;
+        let incremented = PureCounter {
+            count: count.downgrade(),
+        };
+        assert!(incremented == counter.props);
+        assert!(counter.change(incremented));
+
+        let unchanged = PureCounter {
+            count: count.downgrade(),
+        };
+        assert!(!counter.change(unchanged));
     }
 }